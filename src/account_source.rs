@@ -0,0 +1,136 @@
+use crate::errors::*;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use async_trait::async_trait;
+use log::debug;
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+/// Where `SolResolver` reads DID accounts from instead of the network's RPC endpoints,
+/// e.g. a local bank in tests or captured account dumps.
+#[async_trait]
+pub trait AccountSource: Send + Sync {
+    /// Reads the account at `address`, `None` when it does not exist, together with the
+    /// slot it was read at.
+    async fn get_account(
+        &self,
+        address: &Pubkey,
+        commitment: CommitmentConfig
+    ) -> Result<(Option<Account>, u64), SolResolverError>;
+}
+
+/// Accounts captured with `solana account <address> --output json`, for replaying a
+/// resolution offline.
+///
+/// Addresses without a dump read as missing accounts, so their DIDs resolve to the
+/// generative document (or not at all in strict mode), exactly as they would on chain.
+/// Dumps carry no slot; every read reports the source's slot, 0 unless set.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureAccountSource {
+    accounts: HashMap<Pubkey, Account>,
+    slot: u64,
+}
+
+impl FixtureAccountSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a single dump file, or every `.json` dump in a directory.
+    pub fn load(path: &Path) -> Result<Self, SolResolverError> {
+        if path.is_dir() { Self::from_dir(path) } else { Self::from_file(path) }
+    }
+
+    /// Loads a single dump file.
+    pub fn from_file(path: &Path) -> Result<Self, SolResolverError> {
+        Self::new().with_dump_file(path)
+    }
+
+    /// Loads every `.json` dump in a directory. Files named after an address, e.g.
+    /// `<PDA>.json`, must contain the dump of that address.
+    pub fn from_dir(dir: &Path) -> Result<Self, SolResolverError> {
+        let entries = std::fs::read_dir(dir).map_err(|e| dump_error(dir, e))?;
+
+        let mut source = Self::new();
+        for entry in entries {
+            let path = entry.map_err(|e| dump_error(dir, e))?.path();
+            if path.extension().map_or(false, |extension| extension == "json") {
+                source = source.with_dump_file(&path)?;
+            }
+        }
+
+        Ok(source)
+    }
+
+    /// Adds the account of a dump file.
+    pub fn with_dump_file(self, path: &Path) -> Result<Self, SolResolverError> {
+        let json = std::fs::read_to_string(path).map_err(|e| dump_error(path, e))?;
+        let (address, account) = parse_dump(&json).map_err(|e| dump_error(path, e))?;
+
+        let file_address = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| Pubkey::from_str(stem).ok());
+        if let Some(file_address) = file_address.filter(|file_address| *file_address != address) {
+            return Err(dump_error(path, format!("contains {} instead of {}", address, file_address)));
+        }
+
+        Ok(self.with_account(address, account))
+    }
+
+    /// Adds the account of a dump, given as the JSON printed by `solana account`.
+    pub fn with_dump(self, json: &str) -> Result<Self, SolResolverError> {
+        let (address, account) = parse_dump(json).map_err(SolResolverError::InvalidAccountDump)?;
+        Ok(self.with_account(address, account))
+    }
+
+    pub fn with_account(mut self, address: Pubkey, account: Account) -> Self {
+        debug!("Loaded fixture account {} ({} bytes)", address, account.data.len());
+        self.accounts.insert(address, account);
+        self
+    }
+
+    /// Slot reported for every read, e.g. the slot the dumps were captured at.
+    pub fn with_slot(mut self, slot: u64) -> Self {
+        self.slot = slot;
+        self
+    }
+
+    /// Addresses of all loaded accounts.
+    pub fn addresses(&self) -> impl Iterator<Item = &Pubkey> {
+        self.accounts.keys()
+    }
+}
+
+#[async_trait]
+impl AccountSource for FixtureAccountSource {
+    async fn get_account(
+        &self,
+        address: &Pubkey,
+        _commitment: CommitmentConfig
+    ) -> Result<(Option<Account>, u64), SolResolverError> {
+        Ok((self.accounts.get(address).cloned(), self.slot))
+    }
+}
+
+// Decodes `{ "pubkey": ..., "account": { "data": [..., "base64"], ... } }`
+fn parse_dump(json: &str) -> Result<(Pubkey, Account), String> {
+    let keyed_account: RpcKeyedAccount = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+    let address = Pubkey::from_str(&keyed_account.pubkey).map_err(|_|
+        format!("invalid address {}", keyed_account.pubkey)
+    )?;
+    let account = keyed_account.account
+        .decode::<Account>()
+        .ok_or_else(|| "account data cannot be decoded".to_string())?;
+
+    Ok((address, account))
+}
+
+fn dump_error(path: &Path, e: impl std::fmt::Display) -> SolResolverError {
+    SolResolverError::InvalidAccountDump(format!("{}: {}", path.display(), e))
+}
@@ -0,0 +1,179 @@
+use crate::did_sol::DidSol;
+use crate::errors::*;
+use crate::resolver::{ DidAccountSnapshot, ResolveOptions, SolResolution, SolResolver };
+
+use std::collections::{ BTreeMap, HashMap };
+use futures::future::join_all;
+use log::{ info, debug, warn };
+use solana_client::rpc_request::MAX_MULTIPLE_ACCOUNTS;
+use solana_sdk::pubkey::Pubkey;
+
+// A DID awaiting its account, with the derived DID account address
+struct PendingDid {
+    did: String,
+    did_pubkey: Pubkey,
+    account_address: Pubkey,
+}
+
+impl SolResolver {
+    /// Resolves many DIDs at once, fetching their DID accounts in `getMultipleAccounts`
+    /// batches of at most 100 per network.
+    ///
+    /// With a quorum, an `AccountSource` or legacy fallback configured, each DID account is
+    /// read on its own instead.
+    ///
+    /// Every DID gets its own result: malformed DIDs, unsupported networks, missing
+    /// accounts in strict mode, undecodable accounts and failed RPC calls only affect the
+    /// DIDs concerned.
+    pub async fn resolve_many(
+        &self,
+        dids: &[&str]
+    ) -> HashMap<String, Result<SolResolution, SolResolverError>> {
+        info!("Resolving {} DIDs", dids.len());
+
+        let mut results = HashMap::new();
+        let mut pending: BTreeMap<String, Vec<PendingDid>> = BTreeMap::new();
+
+        for did in dids {
+            if results.contains_key(*did) {
+                continue;
+            }
+            if let Some(resolution) = self.cache.get(did) {
+                results.insert(did.to_string(), Ok(resolution));
+                continue;
+            }
+
+            match did.parse::<DidSol>() {
+                Ok(did_sol) => {
+                    let did_pubkey = did_sol.pubkey();
+                    let (account_address, _) = self.derive_did_account(&did_sol);
+                    let network_dids = pending.entry(did_sol.network().to_string()).or_default();
+                    if !network_dids.iter().any(|p| p.did == *did) {
+                        network_dids.push(PendingDid {
+                            did: did.to_string(),
+                            did_pubkey,
+                            account_address,
+                        });
+                    }
+                }
+                Err(e) => {
+                    results.insert(did.to_string(), Err(e.into()));
+                }
+            }
+        }
+
+        for (network, network_dids) in pending {
+            let rpc_pool = match self.rpc_pool(&network) {
+                Ok(rpc_pool) => rpc_pool,
+                Err(_) => {
+                    for pending_did in network_dids {
+                        results.insert(
+                            pending_did.did,
+                            Err(SolResolverError::UnsupportedNetwork(network.clone()))
+                        );
+                    }
+                    continue;
+                }
+            };
+
+            // quorum reads compare a single account across endpoints, account sources
+            // have no batch reads and legacy fallback reads a second account for missing
+            // ones, so DIDs are read one by one instead of in getMultipleAccounts batches
+            let per_did_reads =
+                self.config().quorum.is_some() ||
+                self.has_account_source() ||
+                self.config().resolve_legacy;
+            if per_did_reads {
+                let reads = network_dids.iter().map(|pending_did| self.fetch_account(&pending_did.did));
+                for (pending_did, snapshot) in network_dids.iter().zip(join_all(reads).await) {
+                    let resolution = snapshot.and_then(|snapshot| self.build_document(&snapshot));
+                    if let Ok(resolution) = &resolution {
+                        self.cache.insert(&pending_did.did, resolution);
+                    }
+                    results.insert(pending_did.did.clone(), resolution);
+                }
+                continue;
+            }
+
+            let network = &network;
+            let batches = network_dids.chunks(MAX_MULTIPLE_ACCOUNTS).map(|batch| {
+                async move {
+                    let addresses: Vec<Pubkey> = batch
+                        .iter()
+                        .map(|p| p.account_address)
+                        .collect();
+                    debug!("Fetching {} DID accounts on {}", addresses.len(), network);
+
+                    let response = rpc_pool.call(|rpc_client| {
+                        let addresses = &addresses;
+                        async move {
+                            rpc_client.get_multiple_accounts_with_config(
+                                addresses,
+                                self.account_info_config(&ResolveOptions::default())
+                            ).await
+                        }
+                    }).await;
+                    (batch, response)
+                }
+            });
+
+            for (batch, response) in join_all(batches).await {
+                match response {
+                    Ok(response) => {
+                        let slot = response.context.slot;
+                        if response.value.len() != batch.len() {
+                            warn!(
+                                "getMultipleAccounts on {} returned {} accounts for {} addresses",
+                                network,
+                                response.value.len(),
+                                batch.len()
+                            );
+                        }
+
+                        let mut accounts = response.value.into_iter();
+                        for pending_did in batch {
+                            let Some(account) = accounts.next() else {
+                                results.insert(
+                                    pending_did.did.clone(),
+                                    Err(
+                                        SolResolverError::RpcError(
+                                            format!("No account returned for {}", pending_did.account_address)
+                                        )
+                                    )
+                                );
+                                continue;
+                            };
+                            let snapshot = DidAccountSnapshot {
+                                did: pending_did.did.clone(),
+                                did_pubkey: pending_did.did_pubkey,
+                                account_address: pending_did.account_address,
+                                owner: account.as_ref().map(|account| account.owner),
+                                data: account.map(|account| account.data),
+                                slot,
+                            };
+                            let resolution = self.build_document(&snapshot);
+                            if let Ok(resolution) = &resolution {
+                                self.cache.insert(&pending_did.did, resolution);
+                            }
+                            results.insert(pending_did.did.clone(), resolution);
+                        }
+                    }
+                    Err(e) => {
+                        let message = match e {
+                            SolResolverError::RpcError(message) => message,
+                            e => e.to_string(),
+                        };
+                        for pending_did in batch {
+                            results.insert(
+                                pending_did.did.clone(),
+                                Err(SolResolverError::RpcError(message.clone()))
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        results
+    }
+}
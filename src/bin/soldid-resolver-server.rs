@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+use didcomm_soldid_resolver::{ server, SolResolver, SolResolverConfig };
+use didcomm_soldid_resolver::config::CONFIG_PATH_VAR;
+use log::info;
+
+// Universal Resolver driver for did:sol
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::init();
+
+    // same file and SOLDID_* environment configuration as the soldid CLI
+    let config_path = std::env::var_os(CONFIG_PATH_VAR).map(PathBuf::from);
+    let config = SolResolverConfig::load(config_path.as_deref())?;
+
+    let listen_addr = std::env::var("LISTEN_ADDR").unwrap_or_else(|_| "0.0.0.0:8080".to_string());
+    let listener = tokio::net::TcpListener::bind(&listen_addr).await?;
+    info!("did:sol driver listening on {}", listen_addr);
+
+    axum::serve(listener, server::router(SolResolver::new(config))).await?;
+
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use clap::{ Parser, Subcommand, ValueEnum };
+use didcomm_soldid_resolver::{ DidSol, FixtureAccountSource, SolResolver, SolResolverConfig };
+use didcomm_soldid_resolver::config::CONFIG_PATH_VAR;
+use serde::Serialize;
+use serde_json::{ json, Value };
+use sol_did::state::{ DidAccount, VerificationMethod };
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+// sol-did verification method flags, in bit order
+const VM_FLAG_NAMES: [&str; 8] = [
+    "authentication",
+    "assertion",
+    "keyAgreement",
+    "capabilityInvocation",
+    "capabilityDelegation",
+    "didDocHidden",
+    "ownershipProof",
+    "protected",
+];
+
+/// Resolve and inspect did:sol identities
+#[derive(Parser)]
+#[command(name = "soldid", version)]
+struct Cli {
+    /// TOML configuration file; defaults to $SOLDID_CONFIG
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// RPC endpoint used instead of the network's default
+    #[arg(long, global = true)]
+    rpc_url: Option<String>,
+    /// sol-did program ID
+    #[arg(long, global = true)]
+    program_id: Option<String>,
+    /// Network used when a bare public key is given instead of a DID
+    #[arg(long, global = true, default_value = "mainnet")]
+    network: String,
+    /// Commitment level DID accounts are read at
+    #[arg(long, global = true, value_enum)]
+    commitment: Option<Commitment>,
+    /// Lowest slot DID accounts may be read at
+    #[arg(long, global = true)]
+    min_context_slot: Option<u64>,
+    /// Read DID accounts from a `solana account --output json` dump, or a directory of
+    /// dumps, instead of RPC
+    #[arg(long, global = true)]
+    account_dump: Option<PathBuf>,
+    /// Fall back to the legacy sol-did program for DIDs without a DID account
+    #[arg(long, global = true)]
+    legacy: bool,
+    /// Output format
+    #[arg(long, global = true, value_enum, default_value_t = Output::Json)]
+    output: Output,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Resolve a DID document
+    Resolve {
+        /// DID or base58 public key
+        did: String,
+        /// Document representation
+        #[arg(long, value_enum, default_value_t = DocumentFormat::W3c)]
+        format: DocumentFormat,
+    },
+    /// Dereference a DID URL to a document, verification method or service
+    Dereference {
+        did_url: String,
+    },
+    /// Dump the decoded DID account
+    Account {
+        /// DID or base58 public key
+        did: String,
+    },
+    /// Print the DID account address derived for a public key
+    Pda {
+        /// base58 public key
+        key: String,
+    },
+    /// Print the transactions that changed the DID account and the changes they made
+    History {
+        /// DID or base58 public key
+        did: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DocumentFormat {
+    Didcomm,
+    W3c,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Commitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    /// Pretty printed JSON
+    Json,
+    /// Single line JSON
+    JsonCompact,
+    /// Human readable text
+    Text,
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let cli = Cli::parse();
+    if let Err(e) = run(cli).await {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    let mut resolver = SolResolver::new(config(&cli)?);
+    if let Some(path) = &cli.account_dump {
+        resolver = resolver.with_account_source(FixtureAccountSource::load(path)?);
+    }
+
+    match &cli.command {
+        Command::Resolve { did, format } => {
+            let did = to_did(did, &cli.network)?;
+            let resolution = resolver.resolve_document(&did).await?;
+            match format {
+                DocumentFormat::Didcomm => print(cli.output, &resolution.did_doc)?,
+                DocumentFormat::W3c => print(cli.output, &resolution.w3c_doc)?,
+            }
+        }
+        Command::Dereference { did_url } => {
+            print(cli.output, &resolver.dereference(did_url).await?)?;
+        }
+        Command::Account { did } => {
+            let did = to_did(did, &cli.network)?;
+            let snapshot = resolver.fetch_account(&did).await?;
+            let (account, version) = match resolver.decode_versioned_account(&snapshot)? {
+                Some((account, version)) => (account_json(&account), json!(version)),
+                None => (Value::Null, Value::Null),
+            };
+
+            let dump =
+                json!({
+                "did": did,
+                "address": snapshot.account_address.to_string(),
+                "slot": snapshot.slot,
+                "accountVersion": version,
+                "account": account,
+            });
+            if cli.output == Output::Text {
+                print_text(&dump, 0);
+            } else {
+                print(cli.output, &dump)?;
+            }
+        }
+        Command::Pda { key } => {
+            let did = DidSol::new(&cli.network, Pubkey::from_str(key)?);
+            let (address, bump) = resolver.derive_did_account(&did);
+            let pda =
+                json!({
+                "did": did.to_string(),
+                "programId": resolver.config().network_program_id(did.network()).to_string(),
+                "address": address.to_string(),
+                "bump": bump,
+            });
+            if cli.output == Output::Text {
+                print_text(&pda, 0);
+            } else {
+                print(cli.output, &pda)?;
+            }
+        }
+        Command::History { did } => {
+            let did = to_did(did, &cli.network)?;
+            let history = resolver.account_history(&did).await?;
+            if cli.output == Output::Text {
+                for entry in &history {
+                    println!(
+                        "{}  slot {}  {}",
+                        entry.signature,
+                        entry.slot,
+                        if entry.failed { "failed" } else { "ok" }
+                    );
+                    for change in &entry.changes {
+                        println!("  {}", serde_json::to_string(change)?);
+                    }
+                }
+            } else {
+                print(cli.output, &history)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Shared file and environment configuration, overridden by command-line flags
+fn config(cli: &Cli) -> Result<SolResolverConfig, Box<dyn std::error::Error>> {
+    let path = cli.config.clone().or_else(|| std::env::var_os(CONFIG_PATH_VAR).map(PathBuf::from));
+    let mut config = SolResolverConfig::load(path.as_deref())?;
+
+    if let Some(commitment) = cli.commitment {
+        config = config.with_commitment(match commitment {
+            Commitment::Processed => CommitmentConfig::processed(),
+            Commitment::Confirmed => CommitmentConfig::confirmed(),
+            Commitment::Finalized => CommitmentConfig::finalized(),
+        });
+    }
+
+    if let Some(min_context_slot) = cli.min_context_slot {
+        config = config.with_min_context_slot(min_context_slot);
+    }
+
+    if let Some(program_id) = &cli.program_id {
+        config = config.with_program_id(Pubkey::from_str(program_id)?);
+    }
+
+    if cli.legacy {
+        config = config.with_resolve_legacy(true);
+    }
+
+    // the override applies to whichever network the DID names
+    if let Some(rpc_url) = &cli.rpc_url {
+        for network in config.networks.values_mut() {
+            network.rpc_urls = vec![rpc_url.clone()];
+        }
+    }
+
+    Ok(config)
+}
+
+// Accepts either a DID or a bare public key on the selected network
+fn to_did(did_or_key: &str, network: &str) -> Result<String, Box<dyn std::error::Error>> {
+    if did_or_key.starts_with("did:") {
+        Ok(did_or_key.to_string())
+    } else {
+        Ok(DidSol::new(network, Pubkey::from_str(did_or_key)?).to_string())
+    }
+}
+
+fn account_json(account: &DidAccount) -> Value {
+    json!({
+        "version": account.version,
+        "bump": account.bump,
+        "nonce": account.nonce,
+        "initialVerificationMethod": verification_method_json(&account.initial_verification_method),
+        "verificationMethods": account.verification_methods.iter().map(verification_method_json).collect::<Vec<_>>(),
+        "services": account.services.iter().map(|service| json!({
+            "fragment": service.fragment,
+            "serviceType": service.service_type,
+            "serviceEndpoint": service.service_endpoint,
+        })).collect::<Vec<_>>(),
+        "nativeControllers": account.native_controllers.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+        "otherControllers": account.other_controllers,
+    })
+}
+
+fn verification_method_json(vm: &VerificationMethod) -> Value {
+    let flags: Vec<&str> = VM_FLAG_NAMES.iter()
+        .enumerate()
+        .filter(|(bit, _)| vm.flags & (1 << bit) != 0)
+        .map(|(_, name)| *name)
+        .collect();
+
+    json!({
+        "fragment": vm.fragment,
+        "methodType": vm.method_type,
+        "keyData": bs58::encode(&vm.key_data).into_string(),
+        "flags": flags,
+    })
+}
+
+fn print<T: Serialize>(output: Output, value: &T) -> Result<(), serde_json::Error> {
+    match output {
+        Output::JsonCompact => println!("{}", serde_json::to_string(value)?),
+        // documents have no separate text rendering
+        Output::Json | Output::Text => println!("{}", serde_json::to_string_pretty(value)?),
+    }
+    Ok(())
+}
+
+fn print_text(value: &Value, indent: usize) {
+    let pad = "  ".repeat(indent);
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::Object(_) | Value::Array(_) => {
+                        println!("{}{}:", pad, key);
+                        print_text(value, indent + 1);
+                    }
+                    _ => println!("{}{}: {}", pad, key, value),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                match value {
+                    Value::Object(_) | Value::Array(_) => {
+                        println!("{}-", pad);
+                        print_text(value, indent + 1);
+                    }
+                    _ => println!("{}- {}", pad, value),
+                }
+            }
+        }
+        _ => println!("{}{}", pad, value),
+    }
+}
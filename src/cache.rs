@@ -0,0 +1,75 @@
+use crate::resolver::SolResolution;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{ Duration, Instant };
+use log::debug;
+
+/// Time- and size-bounded cache of resolved documents keyed by DID.
+#[derive(Debug)]
+pub(crate) struct DocumentCache {
+    ttl: Option<Duration>,
+    capacity: usize,
+    entries: Mutex<HashMap<String, (Instant, SolResolution)>>,
+}
+
+impl DocumentCache {
+    /// Creates a cache of at most `capacity` documents whose entries live for `ttl`;
+    /// `None` disables caching.
+    pub fn new(ttl: Option<Duration>, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, did: &str) -> Option<SolResolution> {
+        let ttl = self.ttl?;
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries.get(did) {
+            Some((cached_at, resolution)) if cached_at.elapsed() < ttl => {
+                debug!("Cache hit for DID: {}", did);
+                Some(resolution.clone())
+            }
+            Some(_) => {
+                entries.remove(did);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, did: &str, resolution: &SolResolution) {
+        let Some(ttl) = self.ttl else {
+            return;
+        };
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+
+        // when full, expired entries go first and then the oldest ones
+        if entries.len() >= self.capacity && !entries.contains_key(did) {
+            entries.retain(|_, (cached_at, _)| cached_at.elapsed() < ttl);
+            while entries.len() >= self.capacity {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (cached_at, _))| *cached_at)
+                    .map(|(did, _)| did.clone());
+                if let Some(oldest) = oldest {
+                    debug!("Evicting cached document for DID: {}", oldest);
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(did.to_string(), (Instant::now(), resolution.clone()));
+    }
+
+    pub fn invalidate(&self, did: &str) {
+        if self.entries.lock().unwrap().remove(did).is_some() {
+            debug!("Invalidated cached document for DID: {}", did);
+        }
+    }
+}
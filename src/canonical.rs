@@ -0,0 +1,114 @@
+use serde_json::Value;
+use sha2::{ Digest, Sha256, Sha512 };
+
+/// Digest of a document hash, encoded as a multihash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DocumentDigest {
+    #[default]
+    Sha256,
+    Sha512,
+}
+
+impl DocumentDigest {
+    /// Multicodec code of the digest.
+    pub fn code(&self) -> u8 {
+        match self {
+            DocumentDigest::Sha256 => 0x12,
+            DocumentDigest::Sha512 => 0x13,
+        }
+    }
+
+    /// Hashes `data` into a multihash: the digest code, the digest length and the digest.
+    pub fn multihash(&self, data: &[u8]) -> Vec<u8> {
+        let digest = match self {
+            DocumentDigest::Sha256 => Sha256::digest(data).to_vec(),
+            DocumentDigest::Sha512 => Sha512::digest(data).to_vec(),
+        };
+
+        // both codes and lengths are below 0x80, so their varints are single bytes
+        let mut multihash = vec![self.code(), digest.len() as u8];
+        multihash.extend(digest);
+        multihash
+    }
+}
+
+/// Serializes a JSON value per RFC 8785 (JSON Canonicalization Scheme): no whitespace,
+/// object members sorted by the UTF-16 code units of their names, and numbers in their
+/// shortest ECMAScript form. Array order is kept.
+pub fn canonicalize(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&format_number(n.as_f64().unwrap_or_default())),
+        Value::String(s) => write_string(out, s),
+        Value::Array(values) => {
+            out.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, value);
+            }
+            out.push(']');
+        }
+        Value::Object(members) => {
+            let mut members: Vec<(&String, &Value)> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (i, (name, value)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, name);
+                out.push(':');
+                write_value(out, value);
+            }
+            out.push('}');
+        }
+    }
+}
+
+// serde_json escapes strings the way JCS requires: `"`, `\` and control characters only,
+// using the short escapes where they exist and lowercase `\u00xx` otherwise
+fn write_string(out: &mut String, s: &str) {
+    out.push_str(&serde_json::to_string(s).expect("Serializing a string cannot fail"));
+}
+
+// ECMAScript Number::toString of a finite double, from its shortest round-trip digits
+fn format_number(n: f64) -> String {
+    if n == 0.0 {
+        return "0".to_string();
+    }
+
+    // `{:e}` gives the shortest digits, e.g. "-1.2345e-7"
+    let scientific = format!("{:e}", n.abs());
+    let (mantissa, exponent) = scientific.split_once('e').expect("Scientific notation has an exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let exponent: i32 = exponent.parse().expect("Scientific notation has an integer exponent");
+
+    let k = digits.len() as i32;
+    // position of the decimal point relative to the digits
+    let point = exponent + 1;
+    let sign = if n < 0.0 { "-" } else { "" };
+
+    let formatted = if k <= point && point <= 21 {
+        format!("{}{}", digits, "0".repeat((point - k) as usize))
+    } else if 0 < point && point <= 21 {
+        format!("{}.{}", &digits[..point as usize], &digits[point as usize..])
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else {
+        let exponent_sign = if point - 1 < 0 { "-" } else { "+" };
+        let fraction = if k > 1 { format!(".{}", &digits[1..]) } else { String::new() };
+        format!("{}{}e{}{}", &digits[..1], fraction, exponent_sign, (point - 1).abs())
+    };
+
+    format!("{}{}", sign, formatted)
+}
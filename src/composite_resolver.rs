@@ -0,0 +1,60 @@
+use crate::errors::*;
+use crate::{ SolResolver, WebResolver };
+
+use async_trait::async_trait;
+use didcomm::{ did::{ DIDDoc, DIDResolver }, error::Error, error::ErrorKind };
+use log::debug;
+
+/// Dispatches resolution to a method-specific resolver based on the DID method.
+pub struct CompositeResolver {
+    resolvers: Vec<(String, Box<dyn DIDResolver>)>,
+}
+
+impl Default for CompositeResolver {
+    /// Resolves did:sol and did:web with default settings.
+    fn default() -> Self {
+        Self::new()
+            .with_resolver("sol", SolResolver::default())
+            .with_resolver("web", WebResolver::default())
+    }
+}
+
+impl CompositeResolver {
+    /// Creates a composite resolver without any registered methods.
+    pub fn new() -> Self {
+        Self { resolvers: vec![] }
+    }
+
+    /// Registers the resolver used for `did:<method>:` identifiers, replacing any existing one.
+    pub fn with_resolver(mut self, method: &str, resolver: impl DIDResolver + 'static) -> Self {
+        self.resolvers.retain(|(m, _)| m != method);
+        self.resolvers.push((method.to_string(), Box::new(resolver)));
+        self
+    }
+}
+
+#[async_trait(?Send)]
+impl DIDResolver for CompositeResolver {
+    async fn resolve(
+        &self,
+        did: &str
+    ) -> std::result::Result<Option<DIDDoc>, didcomm::error::Error> {
+        let method = did
+            .strip_prefix("did:")
+            .and_then(|rest| rest.split(':').next())
+            .ok_or_else(|| Error::msg(ErrorKind::Malformed, format!("Invalid DID format: {}", did)))?;
+        debug!("Dispatching DID method: {}", method);
+
+        let (_, resolver) = self.resolvers
+            .iter()
+            .find(|(m, _)| m == method)
+            .ok_or_else(||
+                Error::msg(
+                    ErrorKind::Unsupported,
+                    SolResolverError::UnsupportedDidMethod(method.to_string()).to_string()
+                )
+            )?;
+
+        resolver.resolve(did).await
+    }
+}
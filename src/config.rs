@@ -1,6 +1,15 @@
+use std::collections::BTreeMap;
 use std::sync::{ LazyLock, Once, Mutex };
+use std::time::Duration;
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
+use crate::constants::*;
+use crate::quorum::QuorumPolicy;
+use crate::rpc::RetryPolicy;
+
+/// Environment variable naming the TOML configuration file read by the binaries.
+pub const CONFIG_PATH_VAR: &str = "SOLDID_CONFIG";
 
 // `INIT` can remain as-is because `Once::new()` is a const fn.
 static INIT: Once = Once::new();
@@ -34,3 +43,246 @@ pub fn reset_program_id() {
         "Invalid default program ID"
     );
 }
+
+/// Connection settings for a single Solana cluster.
+#[derive(Debug, Clone)]
+pub struct NetworkConfig {
+    /// RPC endpoints in failover order
+    pub rpc_urls: Vec<String>,
+    /// Pubsub endpoint; derived from the primary RPC URL when unset.
+    pub ws_url: Option<String>,
+    /// sol-did program ID deployed on this cluster; falls back to the resolver's program ID
+    /// when unset.
+    pub program_id: Option<Pubkey>,
+    /// HTTP headers sent with every RPC request, e.g. API keys of hosted RPC providers.
+    /// The pubsub client cannot send custom headers, so `SolResolver::watch` connects
+    /// without them; providers that authenticate websockets by header need the key in
+    /// `ws_url` instead.
+    pub headers: BTreeMap<String, String>,
+}
+
+impl NetworkConfig {
+    pub fn new(rpc_url: &str) -> Self {
+        Self::with_endpoints(&[rpc_url])
+    }
+
+    /// Creates a network served by several RPC endpoints, tried in the given order.
+    pub fn with_endpoints(rpc_urls: &[&str]) -> Self {
+        Self {
+            rpc_urls: rpc_urls
+                .iter()
+                .map(|url| url.to_string())
+                .collect(),
+            ws_url: None,
+            program_id: None,
+            headers: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_ws_url(mut self, ws_url: &str) -> Self {
+        self.ws_url = Some(ws_url.to_string());
+        self
+    }
+
+    pub fn with_program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Returns the primary RPC URL.
+    pub fn rpc_url(&self) -> &str {
+        self.rpc_urls.first().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Returns the pubsub endpoint, following the Solana convention of serving it on the
+    /// RPC port + 1 when the RPC URL carries an explicit port.
+    pub fn websocket_url(&self) -> String {
+        if let Some(ws_url) = &self.ws_url {
+            return ws_url.clone();
+        }
+
+        let ws_url = self.rpc_url().replacen("https://", "wss://", 1).replacen("http://", "ws://", 1);
+        match ws_url.rsplit_once(':') {
+            Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) && !port.is_empty() => {
+                let port: u16 = port.parse().unwrap_or(0);
+                format!("{}:{}", host, port.saturating_add(1))
+            }
+            _ => ws_url,
+        }
+    }
+}
+
+/// Settings for a `SolResolver` instance.
+#[derive(Debug, Clone)]
+pub struct SolResolverConfig {
+    /// Clusters keyed by the network name used in the DID ("mainnet", "devnet", ...).
+    pub networks: BTreeMap<String, NetworkConfig>,
+    /// sol-did program ID; falls back to the globally initialized program ID when unset.
+    pub program_id: Option<Pubkey>,
+    /// When set, DIDs without an on-chain account are reported as not found instead of
+    /// resolving to their generative (chainless) document.
+    pub strict: bool,
+    /// Retry, backoff and failover behaviour of RPC calls.
+    pub retry_policy: RetryPolicy,
+    /// Commitment level DID accounts are read at.
+    pub commitment: CommitmentConfig,
+    /// Lowest slot DID accounts may be read at; RPC nodes behind it are not used.
+    pub min_context_slot: Option<u64>,
+    /// When set, DID accounts are only accepted once enough of the network's RPC
+    /// endpoints return the same account.
+    pub quorum: Option<QuorumPolicy>,
+    /// Timeout of a single RPC request.
+    pub timeout: Duration,
+    /// `Cache-Control` max-age advertised for resolution results served over HTTP.
+    pub cache_max_age: Duration,
+    /// How long resolved documents are cached in memory; `None` disables the cache.
+    pub cache_ttl: Option<Duration>,
+    /// Most documents kept in the in-memory cache; expired and then the oldest documents
+    /// are evicted to make room.
+    pub cache_capacity: usize,
+    /// When set, documents list an X25519 key-agreement key derived from each Ed25519 key
+    /// usable for key agreement (`#default` and any Ed25519 `keyAgreement` key), so DIDComm
+    /// messages can be encrypted to did:sol identities.
+    pub derive_key_agreement: bool,
+    /// When set, DIDs without a `DidAccount` fall back to the `SolData` account of the
+    /// legacy (pre-Anchor) sol-did program before resolving to their generative document.
+    /// Costs an extra account read for every DID without an on-chain account.
+    pub resolve_legacy: bool,
+}
+
+impl Default for SolResolverConfig {
+    fn default() -> Self {
+        let networks = [
+            ("mainnet", MAINNET_RPC),
+            ("testnet", TESTNET_RPC),
+            ("devnet", DEVNET_RPC),
+            ("localnet", LOCALNET_RPC),
+        ]
+            .into_iter()
+            .map(|(name, rpc_url)| (name.to_string(), NetworkConfig::new(rpc_url)))
+            .collect();
+
+        Self {
+            networks,
+            program_id: None,
+            strict: false,
+            retry_policy: RetryPolicy::default(),
+            commitment: CommitmentConfig::finalized(),
+            min_context_slot: None,
+            quorum: None,
+            timeout: Duration::from_secs(30),
+            cache_max_age: Duration::ZERO,
+            cache_ttl: None,
+            cache_capacity: 10_000,
+            derive_key_agreement: false,
+            resolve_legacy: false,
+        }
+    }
+}
+
+impl SolResolverConfig {
+    /// Registers a cluster under `name`, e.g. a private cluster resolving
+    /// `did:sol:<name>:<key>` identifiers, replacing any existing one.
+    pub fn with_network(mut self, name: &str, network: NetworkConfig) -> Self {
+        self.networks.insert(name.to_string(), network);
+        self
+    }
+
+    /// Sets the RPC URL used for `network`, adding the network if needed.
+    pub fn with_rpc_url(mut self, network: &str, rpc_url: &str) -> Self {
+        self.networks.insert(network.to_string(), NetworkConfig::new(rpc_url));
+        self
+    }
+
+    /// Sets the ordered RPC endpoints used for `network`, adding the network if needed.
+    pub fn with_rpc_urls(mut self, network: &str, rpc_urls: &[&str]) -> Self {
+        self.networks.insert(network.to_string(), NetworkConfig::with_endpoints(rpc_urls));
+        self
+    }
+
+    /// Sets the pubsub URL of an already configured network.
+    pub fn with_ws_url(mut self, network: &str, ws_url: &str) -> Self {
+        if let Some(network) = self.networks.get_mut(network) {
+            network.ws_url = Some(ws_url.to_string());
+        }
+        self
+    }
+
+    pub fn with_program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    pub fn with_min_context_slot(mut self, min_context_slot: u64) -> Self {
+        self.min_context_slot = Some(min_context_slot);
+        self
+    }
+
+    pub fn with_quorum(mut self, quorum: QuorumPolicy) -> Self {
+        self.quorum = Some(quorum);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_cache_max_age(mut self, cache_max_age: Duration) -> Self {
+        self.cache_max_age = cache_max_age;
+        self
+    }
+
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = Some(cache_ttl);
+        self
+    }
+
+    pub fn with_cache_capacity(mut self, cache_capacity: usize) -> Self {
+        self.cache_capacity = cache_capacity;
+        self
+    }
+
+    pub fn with_derive_key_agreement(mut self, derive_key_agreement: bool) -> Self {
+        self.derive_key_agreement = derive_key_agreement;
+        self
+    }
+
+    pub fn with_resolve_legacy(mut self, resolve_legacy: bool) -> Self {
+        self.resolve_legacy = resolve_legacy;
+        self
+    }
+
+    /// Returns the sol-did program ID used for PDA derivation.
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id.unwrap_or_else(get_program_id)
+    }
+
+    /// Returns the sol-did program ID of `network`, which may override the resolver's.
+    pub fn network_program_id(&self, network: &str) -> Pubkey {
+        self.networks
+            .get(network)
+            .and_then(|network| network.program_id)
+            .unwrap_or_else(|| self.program_id())
+    }
+}
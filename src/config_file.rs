@@ -0,0 +1,343 @@
+use crate::config::{ NetworkConfig, SolResolverConfig, CONFIG_PATH_VAR };
+use crate::did_sol::is_valid_network_name;
+use crate::errors::*;
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+use log::warn;
+use reqwest::header::{ HeaderName, HeaderValue };
+use serde::Deserialize;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+const ENV_PREFIX: &str = "SOLDID_";
+const ENV_NETWORK_PREFIX: &str = "SOLDID_NETWORK_";
+
+// On-disk form of `SolResolverConfig`; every field is optional and overrides the defaults
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    program_id: Option<String>,
+    commitment: Option<String>,
+    min_context_slot: Option<u64>,
+    strict: Option<bool>,
+    timeout_secs: Option<u64>,
+    derive_key_agreement: Option<bool>,
+    resolve_legacy: Option<bool>,
+    #[serde(default)]
+    cache: CacheSection,
+    #[serde(default)]
+    networks: BTreeMap<String, NetworkSection>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CacheSection {
+    ttl_secs: Option<u64>,
+    max_age_secs: Option<u64>,
+    capacity: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NetworkSection {
+    rpc_urls: Option<Vec<String>>,
+    ws_url: Option<String>,
+    program_id: Option<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+}
+
+impl SolResolverConfig {
+    /// Loads the configuration the binaries use: the defaults, overridden by the TOML file
+    /// at `path` (if any) and then by `SOLDID_*` environment variables.
+    pub fn load(path: Option<&Path>) -> Result<Self, SolResolverError> {
+        let config = match path {
+            Some(path) => Self::from_toml_file(path)?,
+            None => Self::default(),
+        };
+
+        config.with_env()
+    }
+
+    /// Reads a TOML configuration file on top of the defaults.
+    pub fn from_toml_file(path: &Path) -> Result<Self, SolResolverError> {
+        let toml = std::fs::read_to_string(path).map_err(|e|
+            SolResolverError::InvalidConfig(format!("{}: {}", path.display(), e))
+        )?;
+
+        Self::from_toml_str(&toml)
+    }
+
+    /// Parses a TOML configuration on top of the defaults.
+    ///
+    /// ```toml
+    /// program_id = "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc"
+    /// commitment = "confirmed"
+    /// strict = true
+    /// timeout_secs = 10
+    /// derive_key_agreement = true
+    /// resolve_legacy = true
+    ///
+    /// [cache]
+    /// ttl_secs = 60
+    /// max_age_secs = 30
+    /// capacity = 10000
+    ///
+    /// [networks.mainnet]
+    /// rpc_urls = ["https://rpc.example.com", "https://api.mainnet-beta.solana.com"]
+    /// headers = { "x-api-key" = "..." }
+    /// ```
+    pub fn from_toml_str(toml: &str) -> Result<Self, SolResolverError> {
+        let file: ConfigFile = toml
+            ::from_str(toml)
+            .map_err(|e| SolResolverError::InvalidConfig(e.to_string()))?;
+
+        Self::default().apply(file)
+    }
+
+    /// Applies `SOLDID_*` environment variable overrides.
+    pub fn with_env(self) -> Result<Self, SolResolverError> {
+        self.with_env_vars(std::env::vars())
+    }
+
+    /// Applies `SOLDID_*` overrides from the given variables, ignoring all others.
+    /// Unknown `SOLDID_*` variables are logged and ignored:
+    ///
+    /// - `SOLDID_PROGRAM_ID`, `SOLDID_COMMITMENT`, `SOLDID_MIN_CONTEXT_SLOT`, `SOLDID_STRICT`,
+    ///   `SOLDID_TIMEOUT_SECS`, `SOLDID_DERIVE_KEY_AGREEMENT`, `SOLDID_RESOLVE_LEGACY`,
+    ///   `SOLDID_CACHE_TTL_SECS` and `SOLDID_CACHE_MAX_AGE_SECS`
+    /// - `SOLDID_NETWORK_<NAME>_RPC_URLS` (comma separated), `SOLDID_NETWORK_<NAME>_WS_URL`
+    ///   and `SOLDID_NETWORK_<NAME>_PROGRAM_ID`
+    /// - `SOLDID_NETWORK_<NAME>_HEADER_<HEADER>`, where `_` in the header name stands for `-`
+    ///
+    /// Network and header names are lowercased.
+    pub fn with_env_vars<I>(self, vars: I) -> Result<Self, SolResolverError>
+        where I: IntoIterator<Item = (String, String)>
+    {
+        let mut file = ConfigFile::default();
+
+        for (name, value) in vars {
+            if let Some(network_var) = name.strip_prefix(ENV_NETWORK_PREFIX) {
+                let Some((network, setting)) = split_network_var(network_var) else {
+                    warn!("Ignoring unknown setting {}", name);
+                    continue;
+                };
+                let section = file.networks.entry(network.to_lowercase()).or_default();
+
+                match setting {
+                    NetworkVar::RpcUrls => {
+                        section.rpc_urls = Some(
+                            value
+                                .split(',')
+                                .map(|url| url.trim().to_string())
+                                .collect()
+                        );
+                    }
+                    NetworkVar::WsUrl => {
+                        section.ws_url = Some(value);
+                    }
+                    NetworkVar::ProgramId => {
+                        section.program_id = Some(value);
+                    }
+                    NetworkVar::Header(header) => {
+                        section.headers.insert(header.to_lowercase().replace('_', "-"), value);
+                    }
+                }
+                continue;
+            }
+
+            match name.strip_prefix(ENV_PREFIX) {
+                Some("PROGRAM_ID") => {
+                    file.program_id = Some(value);
+                }
+                Some("COMMITMENT") => {
+                    file.commitment = Some(value);
+                }
+                Some("MIN_CONTEXT_SLOT") => {
+                    file.min_context_slot = Some(parse_env(&name, &value)?);
+                }
+                Some("STRICT") => {
+                    file.strict = Some(parse_env(&name, &value)?);
+                }
+                Some("TIMEOUT_SECS") => {
+                    file.timeout_secs = Some(parse_env(&name, &value)?);
+                }
+                Some("DERIVE_KEY_AGREEMENT") => {
+                    file.derive_key_agreement = Some(parse_env(&name, &value)?);
+                }
+                Some("RESOLVE_LEGACY") => {
+                    file.resolve_legacy = Some(parse_env(&name, &value)?);
+                }
+                Some("CACHE_TTL_SECS") => {
+                    file.cache.ttl_secs = Some(parse_env(&name, &value)?);
+                }
+                Some("CACHE_MAX_AGE_SECS") => {
+                    file.cache.max_age_secs = Some(parse_env(&name, &value)?);
+                }
+                Some("CACHE_CAPACITY") => {
+                    file.cache.capacity = Some(parse_env(&name, &value)?);
+                }
+                // SOLDID_CONFIG names the file rather than a setting
+                Some(_) if name == CONFIG_PATH_VAR => {}
+                // a typo must not take the resolver down, but should not go unnoticed either
+                Some(_) => warn!("Ignoring unknown setting {}", name),
+                None => {}
+            }
+        }
+
+        self.apply(file)
+    }
+
+    // Validates `file` and applies its settings on top of `self`
+    fn apply(mut self, file: ConfigFile) -> Result<Self, SolResolverError> {
+        if let Some(program_id) = &file.program_id {
+            self.program_id = Some(parse_program_id("program_id", program_id)?);
+        }
+        if let Some(commitment) = &file.commitment {
+            self.commitment = parse_commitment(commitment)?;
+        }
+        if let Some(min_context_slot) = file.min_context_slot {
+            self.min_context_slot = Some(min_context_slot);
+        }
+        if let Some(strict) = file.strict {
+            self.strict = strict;
+        }
+        if let Some(timeout_secs) = file.timeout_secs {
+            if timeout_secs == 0 {
+                return Err(SolResolverError::InvalidConfig("timeout_secs must be positive".to_string()));
+            }
+            self.timeout = Duration::from_secs(timeout_secs);
+        }
+        if let Some(derive_key_agreement) = file.derive_key_agreement {
+            self.derive_key_agreement = derive_key_agreement;
+        }
+        if let Some(resolve_legacy) = file.resolve_legacy {
+            self.resolve_legacy = resolve_legacy;
+        }
+        if let Some(ttl_secs) = file.cache.ttl_secs {
+            // a TTL of zero disables the cache
+            self.cache_ttl = (ttl_secs > 0).then(|| Duration::from_secs(ttl_secs));
+        }
+        if let Some(max_age_secs) = file.cache.max_age_secs {
+            self.cache_max_age = Duration::from_secs(max_age_secs);
+        }
+        if let Some(capacity) = file.cache.capacity {
+            self.cache_capacity = capacity;
+        }
+
+        for (name, section) in file.networks {
+            let network = apply_network(&name, self.networks.remove(&name), section)?;
+            self.networks.insert(name, network);
+        }
+
+        Ok(self)
+    }
+}
+
+// Validates a network section and applies it on top of the existing network, if any
+fn apply_network(
+    name: &str,
+    existing: Option<NetworkConfig>,
+    section: NetworkSection
+) -> Result<NetworkConfig, SolResolverError> {
+    if !is_valid_network_name(name) {
+        return Err(SolResolverError::InvalidConfig(format!("Invalid network name: {}", name)));
+    }
+
+    let mut network = match (existing, section.rpc_urls) {
+        (Some(mut network), Some(rpc_urls)) => {
+            network.rpc_urls = rpc_urls;
+            network
+        }
+        (Some(network), None) => network,
+        (None, Some(rpc_urls)) => NetworkConfig {
+            rpc_urls,
+            ..NetworkConfig::new("")
+        },
+        (None, None) => {
+            return Err(
+                SolResolverError::InvalidConfig(format!("Network {} has no rpc_urls", name))
+            );
+        }
+    };
+
+    if network.rpc_urls.is_empty() {
+        return Err(SolResolverError::InvalidConfig(format!("Network {} has no rpc_urls", name)));
+    }
+    for rpc_url in &network.rpc_urls {
+        validate_url(name, rpc_url, &["http", "https"])?;
+    }
+
+    if let Some(ws_url) = section.ws_url {
+        validate_url(name, &ws_url, &["ws", "wss"])?;
+        network.ws_url = Some(ws_url);
+    }
+    if let Some(program_id) = &section.program_id {
+        let setting = format!("networks.{}.program_id", name);
+        network.program_id = Some(parse_program_id(&setting, program_id)?);
+    }
+    for (header, value) in section.headers {
+        if HeaderName::from_bytes(header.as_bytes()).is_err() || HeaderValue::from_str(&value).is_err() {
+            return Err(
+                SolResolverError::InvalidConfig(format!("Invalid header {} for network {}", header, name))
+            );
+        }
+        network.headers.insert(header, value);
+    }
+
+    Ok(network)
+}
+
+enum NetworkVar<'a> {
+    RpcUrls,
+    WsUrl,
+    ProgramId,
+    Header(&'a str),
+}
+
+// Splits `<NAME>_<SETTING>` of a SOLDID_NETWORK_ variable
+fn split_network_var(var: &str) -> Option<(&str, NetworkVar<'_>)> {
+    if let Some((network, header)) = var.split_once("_HEADER_") {
+        return Some((network, NetworkVar::Header(header)));
+    }
+
+    [
+        ("_RPC_URLS", NetworkVar::RpcUrls),
+        ("_WS_URL", NetworkVar::WsUrl),
+        ("_PROGRAM_ID", NetworkVar::ProgramId),
+    ]
+        .into_iter()
+        .find_map(|(suffix, setting)| var.strip_suffix(suffix).map(|network| (network, setting)))
+}
+
+fn parse_env<T: FromStr>(name: &str, value: &str) -> Result<T, SolResolverError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| SolResolverError::InvalidConfig(format!("Invalid value for {}: {}", name, value)))
+}
+
+fn parse_program_id(setting: &str, program_id: &str) -> Result<Pubkey, SolResolverError> {
+    Pubkey::from_str(program_id).map_err(|_|
+        SolResolverError::InvalidConfig(format!("Invalid {}: {}", setting, program_id))
+    )
+}
+
+fn parse_commitment(commitment: &str) -> Result<CommitmentConfig, SolResolverError> {
+    match commitment {
+        "processed" => Ok(CommitmentConfig::processed()),
+        "confirmed" => Ok(CommitmentConfig::confirmed()),
+        "finalized" => Ok(CommitmentConfig::finalized()),
+        _ => Err(SolResolverError::InvalidConfig(format!("Invalid commitment: {}", commitment))),
+    }
+}
+
+fn validate_url(network: &str, url: &str, schemes: &[&str]) -> Result<(), SolResolverError> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if schemes.contains(&parsed.scheme()) => Ok(()),
+        _ => Err(SolResolverError::InvalidConfig(format!("Invalid URL for network {}: {}", network, url))),
+    }
+}
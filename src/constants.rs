@@ -4,8 +4,26 @@ pub const MAINNET_RPC: &str = "https://api.mainnet-beta.solana.com";
 pub const TESTNET_RPC: &str = "https://api.testnet.solana.com";
 pub const DEVNET_RPC: &str = "https://api.devnet.solana.com";
 pub const LOCALNET_RPC: &str = "http://127.0.0.1:8899"; // Local validator
-/// Regex to validate did:sol format
-pub const DID_SOL_REGEX: &str =
-    r"^did:sol(?::(testnet|devnet|localnet))?:([1-9A-HJ-NP-Za-km-z]{40,48})$";
 // solana did program id
 pub const DID_PROGRAM_ID: &str = "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc";
+// Seed of the DID account PDA
+pub const DID_ACCOUNT_SEED: &str = "did-account";
+// pre-Anchor sol-did program storing `SolData` accounts, and the seed of their PDA
+pub const LEGACY_DID_PROGRAM_ID: &str = "idDa4XeCjVwKcprVAo812coUQbovSZ4kDGJf2sPaBnM";
+pub const LEGACY_DID_ACCOUNT_SEED: &str = "solid";
+// sol-did verification method flags
+pub const VM_FLAG_AUTHENTICATION: u16 = 1 << 0;
+pub const VM_FLAG_ASSERTION: u16 = 1 << 1;
+pub const VM_FLAG_KEY_AGREEMENT: u16 = 1 << 2;
+pub const VM_FLAG_CAPABILITY_INVOCATION: u16 = 1 << 3;
+pub const VM_FLAG_CAPABILITY_DELEGATION: u16 = 1 << 4;
+pub const VM_FLAG_DID_DOC_HIDDEN: u16 = 1 << 5;
+pub const VM_FLAG_OWNERSHIP_PROOF: u16 = 1 << 6;
+pub const VM_FLAG_PROTECTED: u16 = 1 << 7;
+
+// Smallest serialized DidAccount: discriminator, version, bump, nonce, an initial
+// verification method with empty fragment and key, and four empty vectors
+pub const DID_ACCOUNT_MIN_SIZE: usize = 8 + 1 + 1 + 8 + (4 + 2 + 1 + 4) + 4 * 4;
+
+// JSON-RPC error code of a node that has not reached a request's minContextSlot
+pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
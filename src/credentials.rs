@@ -0,0 +1,384 @@
+use crate::canonical::canonicalize;
+use crate::did_sol::DidSol;
+use crate::errors::*;
+use crate::resolver::SolResolver;
+use crate::verify::{ decode_multibase, CompactJws, VerificationRelationship };
+use crate::w3c_doc::VerificationMethod;
+
+use std::sync::Arc;
+use std::time::{ SystemTime, UNIX_EPOCH };
+use async_trait::async_trait;
+use base64::{ engine::general_purpose::URL_SAFE_NO_PAD, Engine };
+use chrono::DateTime;
+use serde_json::{ Map, Value };
+use sha2::{ Digest, Sha256 };
+
+/// RDF Dataset Canonicalization (URDNA2015 / RDFC-1.0) of JSON-LD documents, needed by
+/// the `Ed25519Signature2018` and `Ed25519Signature2020` suites.
+///
+/// JSON-LD processing needs the documents' contexts, so it is left to the caller's
+/// JSON-LD implementation and its document loader.
+#[async_trait]
+pub trait RdfCanonicalizer: Send + Sync {
+    /// Canonical N-Quads of `document`.
+    async fn canonicalize(&self, document: &Value) -> Result<String, SolResolverError>;
+}
+
+/// A credential whose proof verified, with the issuer's verification method.
+#[derive(Debug, Clone)]
+pub struct VerifiedCredential {
+    /// The credential; for a JWT-VC, its `vc` claim.
+    pub credential: Value,
+    pub verification_method: VerificationMethod,
+}
+
+/// A presentation whose proof verified, with the holder's verification method and the
+/// credentials it presents, each verified against its issuer.
+#[derive(Debug, Clone)]
+pub struct VerifiedPresentation {
+    /// The presentation; for a JWT-VP, its `vp` claim.
+    pub presentation: Value,
+    pub verification_method: VerificationMethod,
+    pub credentials: Vec<VerifiedCredential>,
+}
+
+/// Verifies Verifiable Credentials and Presentations issued by did:sol DIDs.
+///
+/// Proofs are checked against the verification method they name, resolved with the
+/// given `SolResolver`: credentials must be signed with an `assertionMethod` key of
+/// their issuer and presentations with an `authentication` key of their holder.
+///
+/// Supported are JWT-VCs and JWT-VPs (`EdDSA`, `ES256K`) and Data Integrity proofs of
+/// the `eddsa-jcs-2022` cryptosuite; `Ed25519Signature2018` and `Ed25519Signature2020`
+/// proofs need an `RdfCanonicalizer`.
+#[derive(Clone)]
+pub struct CredentialVerifier {
+    resolver: SolResolver,
+    rdf_canonicalizer: Option<Arc<dyn RdfCanonicalizer>>,
+}
+
+impl CredentialVerifier {
+    pub fn new(resolver: SolResolver) -> Self {
+        Self {
+            resolver,
+            rdf_canonicalizer: None,
+        }
+    }
+
+    pub fn with_rdf_canonicalizer(mut self, canonicalizer: impl RdfCanonicalizer + 'static) -> Self {
+        self.rdf_canonicalizer = Some(Arc::new(canonicalizer));
+        self
+    }
+
+    /// Verifies the Data Integrity proof of a credential, its issuer and that it has not
+    /// expired (`validUntil`, or `expirationDate` of VC Data Model 1.1).
+    pub async fn verify_credential(&self, credential: &Value) -> Result<VerifiedCredential, SolResolverError> {
+        let verification_method = self.verify_proof(credential, VerificationRelationship::AssertionMethod).await?;
+        check_party(credential, "issuer", &verification_method, true)?;
+        check_expiry(credential)?;
+
+        Ok(VerifiedCredential {
+            credential: credential.clone(),
+            verification_method,
+        })
+    }
+
+    /// Verifies the Data Integrity proof of a presentation and every credential it
+    /// presents, either embedded with a proof or as a JWT-VC.
+    pub async fn verify_presentation(
+        &self,
+        presentation: &Value
+    ) -> Result<VerifiedPresentation, SolResolverError> {
+        let verification_method = self.verify_proof(presentation, VerificationRelationship::Authentication).await?;
+        check_party(presentation, "holder", &verification_method, false)?;
+
+        Ok(VerifiedPresentation {
+            presentation: presentation.clone(),
+            verification_method,
+            credentials: self.verify_presented_credentials(presentation).await?,
+        })
+    }
+
+    /// Verifies a JWT-VC: a compact JWS whose `kid` names an issuer's method and whose
+    /// `iss` is the issuer. The `vc` claim's own expiry is checked alongside `exp`.
+    pub async fn verify_jwt_credential(&self, jwt: &str) -> Result<VerifiedCredential, SolResolverError> {
+        let (claims, verification_method) = self.verify_jwt(
+            jwt,
+            VerificationRelationship::AssertionMethod
+        ).await?;
+        let credential = claim(&claims, "vc")?;
+        check_expiry(&credential)?;
+
+        Ok(VerifiedCredential {
+            credential,
+            verification_method,
+        })
+    }
+
+    /// Verifies a JWT-VP signed by its holder (`iss`) and every credential it presents.
+    pub async fn verify_jwt_presentation(&self, jwt: &str) -> Result<VerifiedPresentation, SolResolverError> {
+        let (claims, verification_method) = self.verify_jwt(
+            jwt,
+            VerificationRelationship::Authentication
+        ).await?;
+        let presentation = claim(&claims, "vp")?;
+
+        Ok(VerifiedPresentation {
+            credentials: self.verify_presented_credentials(&presentation).await?,
+            presentation,
+            verification_method,
+        })
+    }
+
+    async fn verify_presented_credentials(
+        &self,
+        presentation: &Value
+    ) -> Result<Vec<VerifiedCredential>, SolResolverError> {
+        let mut credentials = vec![];
+        for credential in one_or_many(presentation.get("verifiableCredential")) {
+            credentials.push(match credential {
+                Value::String(jwt) => self.verify_jwt_credential(jwt).await?,
+                credential => self.verify_credential(credential).await?,
+            });
+        }
+
+        Ok(credentials)
+    }
+
+    // Verifies the JWS of a JWT and its validity period, returning its claims
+    async fn verify_jwt(
+        &self,
+        jwt: &str,
+        relationship: VerificationRelationship
+    ) -> Result<(Value, VerificationMethod), SolResolverError> {
+        let jws = CompactJws::parse(jwt)?;
+        // a JWT is a plain JWS, so its payload is always base64url encoded
+        if !jws.header.b64 || !jws.header.crit.is_empty() {
+            return Err(
+                SolResolverError::InvalidCredential("JWT header has b64 or crit members".to_string())
+            );
+        }
+        let claims: Value = URL_SAFE_NO_PAD.decode(jws.encoded_payload)
+            .ok()
+            .and_then(|claims| serde_json::from_slice(&claims).ok())
+            .ok_or_else(|| SolResolverError::InvalidCredential("Invalid JWT claims".to_string()))?;
+        let iss = claims
+            .get("iss")
+            .and_then(Value::as_str)
+            .ok_or_else(|| SolResolverError::InvalidCredential("JWT has no iss claim".to_string()))?;
+
+        // a relative kid names a method of the issuer
+        let kid = match jws.header.kid.as_deref() {
+            Some(kid) if kid.starts_with('#') => format!("{}{}", iss, kid),
+            Some(kid) => kid.to_string(),
+            None => {
+                return Err(SolResolverError::InvalidSignature("JWS header has no kid".to_string()));
+            }
+        };
+
+        let signing_input = format!("{}.{}", jws.encoded_header, jws.encoded_payload);
+        let verification_method = self.resolver.verify_with_algorithm(
+            &kid,
+            &jws.header.alg,
+            signing_input.as_bytes(),
+            &jws.signature,
+            relationship
+        ).await?;
+        if !controls(iss, &verification_method) {
+            return Err(
+                SolResolverError::InvalidCredential(
+                    format!("JWT issuer {} does not control {}", iss, verification_method.id)
+                )
+            );
+        }
+
+        // NumericDate claims may carry fractional seconds
+        let now = unix_now() as f64;
+        if claims.get("exp").and_then(Value::as_f64).is_some_and(|exp| exp <= now) {
+            return Err(SolResolverError::InvalidCredential("JWT has expired".to_string()));
+        }
+        if claims.get("nbf").and_then(Value::as_f64).is_some_and(|nbf| nbf > now) {
+            return Err(SolResolverError::InvalidCredential("JWT is not valid yet".to_string()));
+        }
+
+        Ok((claims, verification_method))
+    }
+
+    // Verifies every proof of a secured document for `relationship`, returning the method
+    // of the first
+    async fn verify_proof(
+        &self,
+        document: &Value,
+        relationship: VerificationRelationship
+    ) -> Result<VerificationMethod, SolResolverError> {
+        let mut unsecured = document
+            .as_object()
+            .cloned()
+            .ok_or_else(|| SolResolverError::InvalidCredential("Document is not an object".to_string()))?;
+        let proofs = unsecured
+            .remove("proof")
+            .ok_or_else(|| SolResolverError::InvalidCredential("Document has no proof".to_string()))?;
+        let unsecured = Value::Object(unsecured);
+
+        let mut verification_method = None;
+        for proof in one_or_many(Some(&proofs)) {
+            let vm = self.verify_single_proof(&unsecured, proof, relationship).await?;
+            verification_method.get_or_insert(vm);
+        }
+
+        verification_method.ok_or_else(|| SolResolverError::InvalidCredential("Document has no proof".to_string()))
+    }
+
+    async fn verify_single_proof(
+        &self,
+        unsecured: &Value,
+        proof: &Value,
+        relationship: VerificationRelationship
+    ) -> Result<VerificationMethod, SolResolverError> {
+        let field = |name: &str| proof.get(name).and_then(Value::as_str);
+        let invalid = |msg: &str| SolResolverError::InvalidCredential(msg.to_string());
+
+        if field("proofPurpose") != Some(relationship.to_string().as_str()) {
+            return Err(
+                SolResolverError::InvalidCredential(format!("Proof purpose is not {}", relationship))
+            );
+        }
+        let kid = field("verificationMethod").ok_or_else(|| invalid("Proof has no verificationMethod"))?;
+
+        // proof options: the proof without its value, under the document's context
+        let mut options: Map<String, Value> = proof.as_object().cloned().unwrap_or_default();
+        options.remove("proofValue");
+        options.remove("jws");
+        if let Some(context) = unsecured.get("@context") {
+            options.insert("@context".to_string(), context.clone());
+        }
+        let options = Value::Object(options);
+
+        let hash_data = match (field("type"), field("cryptosuite")) {
+            (Some("DataIntegrityProof"), Some("eddsa-jcs-2022")) =>
+                hash_data(canonicalize(&options), canonicalize(unsecured)),
+            (Some("Ed25519Signature2018" | "Ed25519Signature2020"), _) => {
+                let canonicalizer = self.rdf_canonicalizer
+                    .as_ref()
+                    .ok_or_else(||
+                        SolResolverError::UnsupportedProof(
+                            "RDF canonicalization needs an RdfCanonicalizer".to_string()
+                        )
+                    )?;
+                hash_data(
+                    canonicalizer.canonicalize(&options).await?,
+                    canonicalizer.canonicalize(unsecured).await?
+                )
+            }
+            (proof_type, cryptosuite) => {
+                return Err(
+                    SolResolverError::UnsupportedProof(
+                        format!("{} {}", proof_type.unwrap_or_default(), cryptosuite.unwrap_or_default())
+                            .trim()
+                            .to_string()
+                    )
+                );
+            }
+        };
+
+        if field("type") == Some("Ed25519Signature2018") {
+            // detached JWS over the hash data, with an unencoded payload
+            let jws = CompactJws::parse(field("jws").ok_or_else(|| invalid("Proof has no jws"))?)?;
+            if jws.header.alg != "EdDSA" {
+                return Err(invalid("Ed25519Signature2018 proofs are EdDSA signatures"));
+            }
+            let signing_input = jws.detached_signing_input(&hash_data)?;
+            return self.resolver.verify_with_algorithm(
+                kid,
+                "EdDSA",
+                &signing_input,
+                &jws.signature,
+                relationship
+            ).await;
+        }
+
+        let signature = field("proofValue")
+            .and_then(decode_multibase)
+            .ok_or_else(|| invalid("Proof has no base58btc proofValue"))?;
+        self.resolver.verify_with_algorithm(kid, "EdDSA", &hash_data, &signature, relationship).await
+    }
+}
+
+// SHA-256 of the canonical proof options followed by SHA-256 of the canonical document
+fn hash_data(options: String, document: String) -> Vec<u8> {
+    let mut hash_data = Sha256::digest(options.as_bytes()).to_vec();
+    hash_data.extend(Sha256::digest(document.as_bytes()));
+    hash_data
+}
+
+// The issuer or holder must control the method that signed the document; only a
+// party that is not `required` may be left out
+fn check_party(
+    document: &Value,
+    name: &str,
+    vm: &VerificationMethod,
+    required: bool
+) -> Result<(), SolResolverError> {
+    let party = match document.get(name) {
+        None if !required => {
+            return Ok(());
+        }
+        None => {
+            return Err(SolResolverError::InvalidCredential(format!("Document has no {}", name)));
+        }
+        Some(Value::String(id)) => Some(id.as_str()),
+        Some(party) => party.get("id").and_then(Value::as_str),
+    };
+
+    if party.is_some_and(|party| controls(party, vm)) {
+        Ok(())
+    } else {
+        Err(SolResolverError::InvalidCredential(format!("The {} does not control {}", name, vm.id)))
+    }
+}
+
+// Rejects a credential past its `validUntil` (VC 2.0) or `expirationDate` (VC 1.1)
+fn check_expiry(credential: &Value) -> Result<(), SolResolverError> {
+    let now = unix_now() as i64;
+    for name in ["validUntil", "expirationDate"] {
+        let Some(value) = credential.get(name) else {
+            continue;
+        };
+        let expiry = value
+            .as_str()
+            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+            .ok_or_else(|| SolResolverError::InvalidCredential(format!("Invalid {}", name)))?;
+        if expiry.timestamp() <= now {
+            return Err(SolResolverError::InvalidCredential("Credential has expired".to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or_default()
+}
+
+// Compares DIDs in their normalized form, as methods name their controller
+fn controls(did: &str, vm: &VerificationMethod) -> bool {
+    did.parse::<DidSol>().is_ok_and(|did| did.to_string() == vm.controller)
+}
+
+fn claim(claims: &Value, name: &str) -> Result<Value, SolResolverError> {
+    claims
+        .get(name)
+        .cloned()
+        .ok_or_else(|| SolResolverError::InvalidCredential(format!("JWT has no {} claim", name)))
+}
+
+fn one_or_many(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        None => vec![],
+        Some(Value::Array(values)) => values.iter().collect(),
+        Some(value) => vec![value],
+    }
+}
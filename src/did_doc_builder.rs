@@ -1,3 +1,6 @@
+use crate::constants::{ VM_FLAG_AUTHENTICATION, VM_FLAG_DID_DOC_HIDDEN, VM_FLAG_KEY_AGREEMENT };
+use crate::key_agreement::{ derived_fragment, x25519_public_key };
+
 use didcomm::did::{ DIDDoc, VerificationMethod, VerificationMethodType };
 use didcomm::did::ServiceKind;
 use didcomm::did::VerificationMaterial;
@@ -34,25 +37,42 @@ impl DidDocBuilder {
     /// Adds on-chain data without overwriting defaults
     pub fn with_onchain_data(mut self, did_account: &DidAccount, did: &str) -> Self {
         debug!("Adding on-chain data to DIDDoc: {}", did);
+        // The default key carries the initial verification method's flags
+        let default_flags = did_account.initial_verification_method.flags;
+        if default_flags & VM_FLAG_DID_DOC_HIDDEN != 0 {
+            let default_id = format!("{}#default", did);
+            self.did_doc.verification_method.retain(|vm| vm.id != default_id);
+        } else {
+            if default_flags & VM_FLAG_KEY_AGREEMENT != 0 {
+                self.did_doc.key_agreement.push(format!("{}#default", did));
+            }
+            if default_flags & VM_FLAG_AUTHENTICATION != 0 {
+                self.did_doc.authentication.push(format!("{}#default", did));
+            }
+        }
+
         // Append on-chain verification methods (without overwriting defaults)
+        let visible_methods: Vec<_> = did_account.verification_methods
+            .iter()
+            .filter(|vm| vm.flags & VM_FLAG_DID_DOC_HIDDEN == 0)
+            .collect();
+
         self.did_doc.key_agreement.extend(
-            did_account.verification_methods
+            visible_methods
                 .iter()
-                .filter(|vm| matches!(vm.flags & 0x02, 0x02))
+                .filter(|vm| vm.flags & VM_FLAG_KEY_AGREEMENT != 0)
                 .map(|vm| format!("{}#{}", did, vm.fragment))
         );
 
         self.did_doc.authentication.extend(
-            did_account.verification_methods
+            visible_methods
                 .iter()
-                .filter(|vm| matches!(vm.flags & 0x01, 0x01))
+                .filter(|vm| vm.flags & VM_FLAG_AUTHENTICATION != 0)
                 .map(|vm| format!("{}#{}", did, vm.fragment))
         );
 
         self.did_doc.verification_method.extend(
-            did_account.verification_methods
-                .iter()
-                .map(|vm| Self::to_didcomm_verification_method(did, vm))
+            visible_methods.iter().map(|vm| Self::to_didcomm_verification_method(did, vm))
         );
 
         self.did_doc.service.extend(
@@ -62,6 +82,24 @@ impl DidDocBuilder {
         self
     }
 
+    /// Adds an X25519 key-agreement method derived from `#default` and from every Ed25519
+    /// method already listed in `keyAgreement`, e.g. `#default-x25519` for `#default`.
+    pub fn with_derived_key_agreement(mut self) -> Self {
+        let default_id = format!("{}#default", self.did_doc.id);
+        let derived: Vec<VerificationMethod> = self.did_doc.verification_method
+            .iter()
+            .filter(|vm| vm.id == default_id || self.did_doc.key_agreement.contains(&vm.id))
+            .filter_map(Self::to_derived_key_agreement_method)
+            .filter(|derived| !self.did_doc.verification_method.iter().any(|vm| vm.id == derived.id))
+            .collect();
+        debug!("Deriving {} key agreement methods for {}", derived.len(), self.did_doc.id);
+
+        self.did_doc.key_agreement.extend(derived.iter().map(|vm| vm.id.clone()));
+        self.did_doc.verification_method.extend(derived);
+
+        self
+    }
+
     /// Returns the final DIDDoc.
     pub fn build(self) -> DIDDoc {
         self.did_doc
@@ -105,6 +143,31 @@ impl DidDocBuilder {
         }
     }
 
+    // X25519 counterpart of an Ed25519 verification method, `None` for any other key
+    fn to_derived_key_agreement_method(vm: &VerificationMethod) -> Option<VerificationMethod> {
+        let public_key_base58 = match (&vm.type_, &vm.verification_material) {
+            (
+                VerificationMethodType::Ed25519VerificationKey2018,
+                VerificationMaterial::Base58 { public_key_base58 },
+            ) => public_key_base58,
+            _ => {
+                return None;
+            }
+        };
+        let ed25519_key = bs58::decode(public_key_base58).into_vec().ok()?;
+        let x25519_key = x25519_public_key(&ed25519_key)?;
+        let (did, fragment) = vm.id.split_once('#')?;
+
+        Some(VerificationMethod {
+            id: format!("{}#{}", did, derived_fragment(fragment)),
+            type_: VerificationMethodType::X25519KeyAgreementKey2019,
+            controller: vm.controller.clone(),
+            verification_material: VerificationMaterial::Base58 {
+                public_key_base58: bs58::encode(x25519_key).into_string(),
+            },
+        })
+    }
+
     /// Converts a Solana service to DIDComm service
     fn to_didcomm_service(did: &str, service: &sol_did::state::Service) -> didcomm::did::Service {
         didcomm::did::Service {
@@ -0,0 +1,149 @@
+use crate::errors::SolResolverError;
+
+use std::fmt;
+use std::str::FromStr;
+use serde::{ de, Deserialize, Deserializer, Serialize, Serializer };
+use solana_sdk::pubkey::Pubkey;
+
+const DID_SOL_PREFIX: &str = "did:sol:";
+const MAINNET: &str = "mainnet";
+
+/// A parsed did:sol identifier: `did:sol:[<network>:]<base58 public key>`.
+///
+/// Mainnet DIDs carry no network segment; `did:sol:mainnet:<key>` is accepted and
+/// normalized to `did:sol:<key>`. Network names are lowercase letters, digits, `-` and `_`;
+/// whether a network is known is decided by the resolver configuration, not the parser.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DidSol {
+    network: String,
+    pubkey: Pubkey,
+}
+
+/// Reasons a string is not a valid did:sol identifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DidSolParseError {
+    /// The identifier does not start with `did:sol:`
+    NotDidSol,
+    /// The identifier has more segments than `did:sol:<network>:<key>`, or an empty one
+    InvalidFormat,
+    /// The network name contains characters other than lowercase letters, digits, `-` and `_`
+    InvalidNetwork(String),
+    /// The method-specific identifier is not base58
+    InvalidBase58(String),
+    /// The method-specific identifier does not decode to a 32 byte public key
+    InvalidKeyLength(usize),
+}
+
+impl fmt::Display for DidSolParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DidSolParseError::NotDidSol => write!(f, "DID does not start with did:sol:"),
+            DidSolParseError::InvalidFormat =>
+                write!(f, "Expected did:sol:[<network>:]<base58 public key>"),
+            DidSolParseError::InvalidNetwork(network) =>
+                write!(f, "Invalid network name: {}", network),
+            DidSolParseError::InvalidBase58(id) => write!(f, "Identifier is not base58: {}", id),
+            DidSolParseError::InvalidKeyLength(len) =>
+                write!(f, "Identifier decodes to {} bytes instead of 32", len),
+        }
+    }
+}
+
+impl std::error::Error for DidSolParseError {}
+
+impl From<DidSolParseError> for SolResolverError {
+    fn from(err: DidSolParseError) -> Self {
+        SolResolverError::InvalidDid(err)
+    }
+}
+
+impl DidSol {
+    /// Builds the DID of `pubkey` on `network`, e.g. `DidSol::new("devnet", keypair.pubkey())`.
+    pub fn new(network: &str, pubkey: Pubkey) -> Self {
+        Self {
+            network: network.to_string(),
+            pubkey,
+        }
+    }
+
+    /// Builds the mainnet DID of `pubkey`.
+    pub fn mainnet(pubkey: Pubkey) -> Self {
+        Self::new(MAINNET, pubkey)
+    }
+
+    /// Network the DID is anchored on, "mainnet" when the DID names none.
+    pub fn network(&self) -> &str {
+        &self.network
+    }
+
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    /// DID URL of a verification method or service, e.g. `did:sol:<key>#default`.
+    pub fn to_did_url(&self, fragment: &str) -> String {
+        format!("{}#{}", self, fragment)
+    }
+}
+
+impl FromStr for DidSol {
+    type Err = DidSolParseError;
+
+    fn from_str(did: &str) -> Result<Self, Self::Err> {
+        let method_specific_id = did.strip_prefix(DID_SOL_PREFIX).ok_or(DidSolParseError::NotDidSol)?;
+
+        let segments: Vec<&str> = method_specific_id.split(':').collect();
+        let (network, id) = match segments.as_slice() {
+            [id] => (MAINNET, *id),
+            [network, id] => (*network, *id),
+            _ => {
+                return Err(DidSolParseError::InvalidFormat);
+            }
+        };
+        if id.is_empty() {
+            return Err(DidSolParseError::InvalidFormat);
+        }
+        if !is_valid_network_name(network) {
+            return Err(DidSolParseError::InvalidNetwork(network.to_string()));
+        }
+
+        let key = bs58
+            ::decode(id)
+            .into_vec()
+            .map_err(|_| DidSolParseError::InvalidBase58(id.to_string()))?;
+        let pubkey = Pubkey::try_from(key.as_slice()).map_err(|_|
+            DidSolParseError::InvalidKeyLength(key.len())
+        )?;
+
+        Ok(Self::new(network, pubkey))
+    }
+}
+
+// Network names are lowercase letters, digits, `-` and `_`
+pub(crate) fn is_valid_network_name(network: &str) -> bool {
+    !network.is_empty() &&
+        network.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+impl fmt::Display for DidSol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.network == MAINNET {
+            write!(f, "{}{}", DID_SOL_PREFIX, self.pubkey)
+        } else {
+            write!(f, "{}{}:{}", DID_SOL_PREFIX, self.network, self.pubkey)
+        }
+    }
+}
+
+impl Serialize for DidSol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DidSol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let did = String::deserialize(deserializer)?;
+        did.parse().map_err(de::Error::custom)
+    }
+}
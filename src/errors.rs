@@ -1,17 +1,126 @@
+use crate::did_sol::DidSolParseError;
+use crate::quorum::QuorumDisagreement;
+
 use std::fmt;
+use solana_sdk::pubkey::Pubkey;
+use didcomm::error::{ Error, ErrorKind };
 
 #[derive(Debug)]
 pub enum SolResolverError {
-    InvalidDidFormat,
-    InvalidSolanaAddress,
+    InvalidDid(DidSolParseError),
+    InvalidDidWebFormat,
+    DocumentIdMismatch,
+    UnsupportedDidMethod(String),
+    UnsupportedNetwork(String),
+    HttpError(String),
+    RpcError(String),
+    DeserializationError(String),
+    NotFound,
+    DidUrlNotFound(String),
+    Deactivated,
+    QuorumNotReached(QuorumDisagreement),
+    InvalidAccountOwner(Pubkey),
+    InvalidAccountDiscriminator,
+    InvalidAccountSize(usize),
+    BumpMismatch {
+        expected: u8,
+        found: u8,
+    },
+    InvalidConfig(String),
+    InvalidKeypair(String),
+    SigningError(String),
+    InvalidAccountDump(String),
+    InvalidLegacyAccount(String),
+    InvalidDidDocument(String),
+    InvalidSignature(String),
+    KeyNotAuthorized {
+        kid: String,
+        relationship: String,
+    },
+    UnsupportedVerificationMethod(String),
+    InvalidCredential(String),
+    UnsupportedProof(String),
 }
 
 impl fmt::Display for SolResolverError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            SolResolverError::InvalidDidFormat => write!(f, "Invalid DID format"),
-            SolResolverError::InvalidSolanaAddress =>
-                write!(f, "Invalid DID format - Missing Solana address"),
+            SolResolverError::InvalidDid(err) => write!(f, "Invalid DID format: {}", err),
+            SolResolverError::InvalidDidWebFormat => write!(f, "Invalid did:web format"),
+            SolResolverError::DocumentIdMismatch =>
+                write!(f, "DID document id does not match the requested DID"),
+            SolResolverError::UnsupportedDidMethod(method) =>
+                write!(f, "Unsupported DID method: {}", method),
+            SolResolverError::UnsupportedNetwork(network) =>
+                write!(f, "Unsupported Solana network: {}", network),
+            SolResolverError::HttpError(msg) => write!(f, "HTTP error: {}", msg),
+            SolResolverError::RpcError(msg) => write!(f, "RPC error: {}", msg),
+            SolResolverError::DeserializationError(msg) =>
+                write!(f, "Anchor Deserialization Error: {}", msg),
+            SolResolverError::NotFound => write!(f, "DID not found"),
+            SolResolverError::DidUrlNotFound(did_url) => write!(f, "DID URL not found: {}", did_url),
+            SolResolverError::Deactivated => write!(f, "DID has been deactivated"),
+            SolResolverError::QuorumNotReached(disagreement) =>
+                write!(f, "RPC quorum not reached: {}", disagreement),
+            SolResolverError::InvalidAccountOwner(owner) =>
+                write!(f, "DID account is not owned by the sol-did program (owner: {})", owner),
+            SolResolverError::InvalidAccountDiscriminator =>
+                write!(f, "Account data is not a DidAccount (discriminator mismatch)"),
+            SolResolverError::InvalidAccountSize(len) =>
+                write!(f, "DID account data length {} is out of bounds", len),
+            SolResolverError::BumpMismatch { expected, found } =>
+                write!(f, "DID account bump {} does not match the derived bump {}", found, expected),
+            SolResolverError::InvalidConfig(msg) => write!(f, "Invalid configuration: {}", msg),
+            SolResolverError::InvalidKeypair(msg) => write!(f, "Invalid keypair file: {}", msg),
+            SolResolverError::SigningError(msg) => write!(f, "Failed to sign transaction: {}", msg),
+            SolResolverError::InvalidAccountDump(msg) => write!(f, "Invalid account dump: {}", msg),
+            SolResolverError::InvalidLegacyAccount(msg) =>
+                write!(f, "Invalid legacy SolData account: {}", msg),
+            SolResolverError::InvalidDidDocument(msg) => write!(f, "Invalid DID document: {}", msg),
+            SolResolverError::InvalidSignature(msg) => write!(f, "Invalid signature: {}", msg),
+            SolResolverError::KeyNotAuthorized { kid, relationship } =>
+                write!(f, "Verification method {} is not authorized for {}", kid, relationship),
+            SolResolverError::UnsupportedVerificationMethod(method) =>
+                write!(f, "Unsupported verification method: {}", method),
+            SolResolverError::InvalidCredential(msg) => write!(f, "Invalid credential: {}", msg),
+            SolResolverError::UnsupportedProof(proof) => write!(f, "Unsupported proof: {}", proof),
         }
     }
 }
+
+impl std::error::Error for SolResolverError {}
+
+impl From<SolResolverError> for Error {
+    fn from(err: SolResolverError) -> Self {
+        let kind = match err {
+            SolResolverError::InvalidDid(_) |
+            SolResolverError::InvalidDidWebFormat |
+            SolResolverError::DocumentIdMismatch |
+            SolResolverError::DeserializationError(_) |
+            SolResolverError::InvalidAccountOwner(_) |
+            SolResolverError::InvalidAccountDiscriminator |
+            SolResolverError::InvalidAccountSize(_) |
+            SolResolverError::BumpMismatch { .. } |
+            SolResolverError::InvalidAccountDump(_) |
+            SolResolverError::InvalidLegacyAccount(_) |
+            SolResolverError::InvalidDidDocument(_) |
+            SolResolverError::InvalidSignature(_) |
+            SolResolverError::InvalidCredential(_) => ErrorKind::Malformed,
+            SolResolverError::UnsupportedDidMethod(_) |
+            SolResolverError::UnsupportedNetwork(_) |
+            SolResolverError::UnsupportedVerificationMethod(_) |
+            SolResolverError::UnsupportedProof(_) => ErrorKind::Unsupported,
+            SolResolverError::HttpError(_) |
+            SolResolverError::RpcError(_) |
+            SolResolverError::QuorumNotReached(_) => ErrorKind::IoError,
+            SolResolverError::NotFound | SolResolverError::Deactivated => ErrorKind::DIDNotResolved,
+            SolResolverError::DidUrlNotFound(_) => ErrorKind::DIDUrlNotFound,
+            SolResolverError::InvalidConfig(_) |
+            SolResolverError::InvalidKeypair(_) |
+            SolResolverError::SigningError(_) |
+            SolResolverError::KeyNotAuthorized { .. } => ErrorKind::IllegalArgument,
+        };
+
+        Error::msg(kind, err.to_string())
+    }
+}
@@ -0,0 +1,203 @@
+use crate::did_sol::DidSol;
+use crate::errors::*;
+use crate::resolver::SolResolver;
+
+use std::str::FromStr;
+use anchor_client::anchor_lang::{ AnchorDeserialize, Discriminator };
+use serde::Serialize;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::UiTransactionEncoding;
+
+/// A transaction that touched a DID account, with the changes it made to the DID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidHistoryEntry {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// Failed transactions changed nothing, but are listed with the changes they attempted
+    pub failed: bool,
+    pub changes: Vec<DidChange>,
+}
+
+/// A sol-did instruction of a transaction, decoded from its instruction data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "instruction", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum DidChange {
+    Initialize {
+        size: u32,
+    },
+    Resize {
+        size: u32,
+    },
+    AddVerificationMethod {
+        fragment: String,
+        method_type: u8,
+        flags: u16,
+    },
+    RemoveVerificationMethod {
+        fragment: String,
+    },
+    SetVmFlags {
+        fragment: String,
+        flags: u16,
+    },
+    AddService {
+        fragment: String,
+        service_type: String,
+        service_endpoint: String,
+    },
+    RemoveService {
+        fragment: String,
+    },
+    SetControllers {
+        native_controllers: Vec<String>,
+        other_controllers: Vec<String>,
+    },
+    Close,
+    /// An instruction this resolver does not decode, by its hex encoded discriminator
+    Other {
+        discriminator: String,
+    },
+}
+
+impl DidChange {
+    /// Decodes the data of a sol-did instruction: the Anchor discriminator followed by
+    /// the borsh serialized arguments.
+    pub fn decode(data: &[u8]) -> Self {
+        let (discriminator, args) = data.split_at(data.len().min(8));
+        Self::decode_args(discriminator, args).unwrap_or_else(|| DidChange::Other {
+            discriminator: discriminator
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        })
+    }
+
+    fn decode_args(discriminator: &[u8], args: &[u8]) -> Option<Self> {
+        use sol_did::instruction::*;
+
+        fn args_of<T: AnchorDeserialize>(args: &[u8]) -> Option<T> {
+            T::deserialize(&mut &args[..]).ok()
+        }
+
+        let change = match discriminator {
+            d if d == Initialize::DISCRIMINATOR => {
+                let Initialize { size, .. } = args_of(args)?;
+                DidChange::Initialize { size }
+            }
+            d if d == Resize::DISCRIMINATOR => {
+                let Resize { size, .. } = args_of(args)?;
+                DidChange::Resize { size }
+            }
+            d if d == AddVerificationMethod::DISCRIMINATOR => {
+                let AddVerificationMethod { verification_method: vm, .. } = args_of(args)?;
+                DidChange::AddVerificationMethod {
+                    fragment: vm.fragment,
+                    method_type: vm.method_type,
+                    flags: vm.flags,
+                }
+            }
+            d if d == RemoveVerificationMethod::DISCRIMINATOR => {
+                let RemoveVerificationMethod { fragment, .. } = args_of(args)?;
+                DidChange::RemoveVerificationMethod { fragment }
+            }
+            d if d == SetVmFlags::DISCRIMINATOR => {
+                let SetVmFlags { flags_vm, .. } = args_of(args)?;
+                DidChange::SetVmFlags {
+                    fragment: flags_vm.fragment,
+                    flags: flags_vm.flags,
+                }
+            }
+            d if d == AddService::DISCRIMINATOR => {
+                let AddService { service, .. } = args_of(args)?;
+                DidChange::AddService {
+                    fragment: service.fragment,
+                    service_type: service.service_type,
+                    service_endpoint: service.service_endpoint,
+                }
+            }
+            d if d == RemoveService::DISCRIMINATOR => {
+                let RemoveService { fragment, .. } = args_of(args)?;
+                DidChange::RemoveService { fragment }
+            }
+            d if d == SetControllers::DISCRIMINATOR => {
+                let SetControllers { set_controllers_arg, .. } = args_of(args)?;
+                DidChange::SetControllers {
+                    native_controllers: set_controllers_arg.native_controllers
+                        .iter()
+                        .map(Pubkey::to_string)
+                        .collect(),
+                    other_controllers: set_controllers_arg.other_controllers,
+                }
+            }
+            d if d == Close::DISCRIMINATOR => DidChange::Close,
+            _ => {
+                return None;
+            }
+        };
+
+        Some(change)
+    }
+}
+
+impl SolResolver {
+    /// Lists the transactions that touched the DID account, newest first, with the
+    /// sol-did instructions each ran.
+    ///
+    /// Only top-level instructions of the DID's sol-did program are decoded; changes made
+    /// through cross-program invocations are not listed.
+    pub async fn account_history(&self, did: &str) -> Result<Vec<DidHistoryEntry>, SolResolverError> {
+        let did_sol: DidSol = did.parse()?;
+        let rpc_pool = self.rpc_pool(did_sol.network())?;
+        let program_id = self.config().network_program_id(did_sol.network());
+        let (did_account_pubkey, _) = self.derive_did_account(&did_sol);
+
+        let signatures = rpc_pool.call(|rpc_client| async move {
+            rpc_client.get_signatures_for_address(&did_account_pubkey).await
+        }).await?;
+
+        let mut history = Vec::with_capacity(signatures.len());
+        for status in signatures {
+            let signature = Signature::from_str(&status.signature).map_err(|e|
+                SolResolverError::RpcError(format!("Invalid signature {}: {}", status.signature, e))
+            )?;
+            let transaction = rpc_pool.call(|rpc_client| async move {
+                rpc_client.get_transaction_with_config(&signature, RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: None,
+                    max_supported_transaction_version: Some(0),
+                }).await
+            }).await?;
+
+            let changes = transaction.transaction.transaction
+                .decode()
+                .map(|transaction| decode_changes(&transaction, &program_id))
+                .unwrap_or_default();
+            history.push(DidHistoryEntry {
+                signature: status.signature,
+                slot: status.slot,
+                block_time: status.block_time,
+                failed: status.err.is_some(),
+                changes,
+            });
+        }
+
+        Ok(history)
+    }
+}
+
+// Decodes the top-level instructions of `program_id` in a transaction
+fn decode_changes(transaction: &VersionedTransaction, program_id: &Pubkey) -> Vec<DidChange> {
+    let account_keys = transaction.message.static_account_keys();
+
+    transaction.message
+        .instructions()
+        .iter()
+        .filter(|instruction| account_keys.get(instruction.program_id_index as usize) == Some(program_id))
+        .map(|instruction| DidChange::decode(&instruction.data))
+        .collect()
+}
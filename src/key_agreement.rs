@@ -0,0 +1,35 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{ Digest, Sha512 };
+
+/// Fragment suffix of the X25519 key-agreement key derived from an Ed25519 key, e.g.
+/// `#default-x25519` for `#default`.
+pub const DERIVED_KEY_AGREEMENT_SUFFIX: &str = "-x25519";
+
+/// Fragment of the X25519 key-agreement key derived from the Ed25519 key at `fragment`.
+pub fn derived_fragment(fragment: &str) -> String {
+    format!("{}{}", fragment, DERIVED_KEY_AGREEMENT_SUFFIX)
+}
+
+/// Maps an Ed25519 public key to its X25519 (Montgomery) form, `None` when the bytes are
+/// not a valid Ed25519 point.
+pub fn x25519_public_key(ed25519_public_key: &[u8]) -> Option<[u8; 32]> {
+    if ed25519_public_key.len() != 32 {
+        return None;
+    }
+
+    let compressed = CompressedEdwardsY::from_slice(ed25519_public_key);
+    Some(compressed.decompress()?.to_montgomery().to_bytes())
+}
+
+/// Derives the X25519 private key matching `x25519_public_key` from an Ed25519 seed: the
+/// clamped lower half of its SHA-512 hash, as used for Ed25519 signing.
+pub fn x25519_private_key(ed25519_seed: &[u8; 32]) -> [u8; 32] {
+    let hash = Sha512::digest(ed25519_seed);
+    let mut scalar = [0u8; 32];
+    scalar.copy_from_slice(&hash[..32]);
+
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+    scalar
+}
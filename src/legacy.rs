@@ -0,0 +1,229 @@
+use crate::constants::*;
+use crate::did_sol::DidSol;
+use crate::errors::SolResolverError;
+
+use std::str::FromStr;
+use anchor_client::anchor_lang::AnchorDeserialize;
+use sol_did::state::{ DidAccount, Service, VerificationMethod };
+use solana_sdk::pubkey::Pubkey;
+
+const DEFAULT_FRAGMENT: &str = "default";
+
+// Verification method types of the sol-did program, by their W3C name
+const METHOD_TYPES: [(&str, u8); 3] = [
+    ("Ed25519VerificationKey2018", 0),
+    ("EcdsaSecp256k1RecoveryMethod2020", 1),
+    ("EcdsaSecp256k1VerificationKey2019", 2),
+];
+
+/// `SolData` account of the legacy (pre-Anchor) sol-did program: a borsh serialized
+/// document without discriminator, zero padded to the account size.
+///
+/// Only the parts with a `DidAccount` counterpart are kept: controller URL fields and
+/// service descriptions are skipped while decoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolData {
+    pub authority: Pubkey,
+    pub version: String,
+    pub controller: Vec<LegacyController>,
+    pub verification_method: Vec<LegacyVerificationMethod>,
+    pub authentication: Vec<String>,
+    pub capability_invocation: Vec<String>,
+    pub capability_delegation: Vec<String>,
+    pub key_agreement: Vec<String>,
+    pub assertion_method: Vec<String>,
+    pub service: Vec<LegacyService>,
+}
+
+/// A controller DID of a `SolData` account, which may live on another cluster.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyController {
+    /// Cluster type: 0 testnet, 1 mainnet-beta, 2 devnet, 3 development
+    pub cluster: u8,
+    pub authority: Pubkey,
+}
+
+impl LegacyController {
+    /// Network name of the controller's cluster, as used in did:sol DIDs.
+    pub fn network(&self) -> Result<&'static str, SolResolverError> {
+        match self.cluster {
+            0 => Ok("testnet"),
+            1 => Ok("mainnet"),
+            2 => Ok("devnet"),
+            3 => Ok("localnet"),
+            cluster => Err(SolResolverError::InvalidLegacyAccount(format!("Unknown cluster type {}", cluster))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyVerificationMethod {
+    pub id: String,
+    pub verification_type: String,
+    pub pubkey: Pubkey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegacyService {
+    pub id: String,
+    pub service_type: String,
+    pub endpoint: String,
+}
+
+pub fn legacy_program_id() -> Pubkey {
+    Pubkey::from_str(LEGACY_DID_PROGRAM_ID).expect("Invalid legacy program ID")
+}
+
+/// Address of the legacy `SolData` account of a DID key.
+pub fn derive_legacy_account(authority: &Pubkey) -> Pubkey {
+    let seeds = [authority.as_ref(), LEGACY_DID_ACCOUNT_SEED.as_bytes()];
+    Pubkey::find_program_address(&seeds, &legacy_program_id()).0
+}
+
+impl SolData {
+    /// Decodes the account data, ignoring the zero padding after the document.
+    pub fn decode(data: &[u8]) -> Result<Self, SolResolverError> {
+        let data = &mut &data[..];
+
+        Ok(Self {
+            authority: read(data)?,
+            version: read(data)?,
+            controller: read_vec(data, |data| {
+                let controller = LegacyController {
+                    cluster: read(data)?,
+                    authority: read(data)?,
+                };
+                read::<String>(data)?;
+                Ok(controller)
+            })?,
+            verification_method: read_vec(data, |data| {
+                Ok(LegacyVerificationMethod {
+                    id: read(data)?,
+                    verification_type: read(data)?,
+                    pubkey: read(data)?,
+                })
+            })?,
+            authentication: read_vec(data, read)?,
+            capability_invocation: read_vec(data, read)?,
+            capability_delegation: read_vec(data, read)?,
+            key_agreement: read_vec(data, read)?,
+            assertion_method: read_vec(data, read)?,
+            service: read_vec(data, |data| {
+                let service = LegacyService {
+                    id: read(data)?,
+                    service_type: read(data)?,
+                    endpoint: read(data)?,
+                };
+                read::<String>(data)?;
+                Ok(service)
+            })?,
+        })
+    }
+
+    /// Maps the document onto the current `DidAccount` model of a DID on `network`:
+    /// relationship lists become verification method flags and the authority's `default`
+    /// key the initial verification method. Controllers on the same network become native
+    /// controllers, those on other clusters `other_controllers` DIDs.
+    pub fn into_did_account(self, network: &str) -> Result<DidAccount, SolResolverError> {
+        let mut native_controllers = Vec::new();
+        let mut other_controllers = Vec::new();
+        for controller in &self.controller {
+            let controller_network = controller.network()?;
+            if controller_network == network {
+                native_controllers.push(controller.authority);
+            } else {
+                other_controllers.push(DidSol::new(controller_network, controller.authority).to_string());
+            }
+        }
+
+        let default_listed = self.verification_method
+            .iter()
+            .any(|vm| fragment_of(&vm.id) == DEFAULT_FRAGMENT && vm.pubkey == self.authority);
+        let mut default_flags = self.relationship_flags(DEFAULT_FRAGMENT) | VM_FLAG_OWNERSHIP_PROOF;
+        if !default_listed {
+            default_flags |= VM_FLAG_DID_DOC_HIDDEN;
+        }
+
+        let mut verification_methods = Vec::new();
+        for vm in &self.verification_method {
+            let fragment = fragment_of(&vm.id);
+            if fragment == DEFAULT_FRAGMENT && vm.pubkey == self.authority {
+                continue;
+            }
+            let method_type = METHOD_TYPES.iter()
+                .find(|(name, _)| *name == vm.verification_type)
+                .map(|(_, method_type)| *method_type)
+                .ok_or_else(||
+                    SolResolverError::InvalidLegacyAccount(
+                        format!("Unknown verification method type {}", vm.verification_type)
+                    )
+                )?;
+
+            verification_methods.push(VerificationMethod {
+                fragment: fragment.to_string(),
+                flags: self.relationship_flags(fragment),
+                method_type,
+                key_data: vm.pubkey.to_bytes().to_vec(),
+            });
+        }
+
+        Ok(DidAccount {
+            version: 0,
+            bump: 0,
+            nonce: 0,
+            initial_verification_method: VerificationMethod {
+                fragment: DEFAULT_FRAGMENT.to_string(),
+                flags: default_flags,
+                method_type: 0,
+                key_data: self.authority.to_bytes().to_vec(),
+            },
+            verification_methods,
+            services: self.service
+                .into_iter()
+                .map(|service| Service {
+                    fragment: fragment_of(&service.id).to_string(),
+                    service_type: service.service_type,
+                    service_endpoint: service.endpoint,
+                })
+                .collect(),
+            native_controllers,
+            other_controllers,
+        })
+    }
+
+    // Flags of the relationship lists naming `fragment`
+    fn relationship_flags(&self, fragment: &str) -> u16 {
+        [
+            (&self.authentication, VM_FLAG_AUTHENTICATION),
+            (&self.assertion_method, VM_FLAG_ASSERTION),
+            (&self.key_agreement, VM_FLAG_KEY_AGREEMENT),
+            (&self.capability_invocation, VM_FLAG_CAPABILITY_INVOCATION),
+            (&self.capability_delegation, VM_FLAG_CAPABILITY_DELEGATION),
+        ]
+            .into_iter()
+            .filter(|(ids, _)| ids.iter().any(|id| fragment_of(id) == fragment))
+            .fold(0, |flags, (_, flag)| flags | flag)
+    }
+}
+
+// Legacy ids are stored as `default`, `#default` or a full `did:sol:...#default` URL
+fn fragment_of(id: &str) -> &str {
+    id.rsplit_once('#').map_or(id, |(_, fragment)| fragment)
+}
+
+fn read<T: AnchorDeserialize>(data: &mut &[u8]) -> Result<T, SolResolverError> {
+    T::deserialize(data).map_err(|e| SolResolverError::InvalidLegacyAccount(e.to_string()))
+}
+
+// Reads a borsh vector item by item, rejecting lengths the remaining data cannot hold
+fn read_vec<T>(
+    data: &mut &[u8],
+    read_item: impl Fn(&mut &[u8]) -> Result<T, SolResolverError>
+) -> Result<Vec<T>, SolResolverError> {
+    let len: u32 = read(data)?;
+    if len as usize > data.len() {
+        return Err(SolResolverError::InvalidLegacyAccount(format!("Invalid vector length {}", len)));
+    }
+
+    (0..len).map(|_| read_item(data)).collect()
+}
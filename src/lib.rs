@@ -1,11 +1,48 @@
+mod batch;
+mod cache;
+mod config_file;
 mod constants;
-mod errors;
 mod did_doc_builder;
+mod key_agreement;
+mod legacy;
 
+pub mod account_source;
+pub mod canonical;
+pub mod credentials;
+pub mod errors;
+pub mod did_sol;
+pub mod history;
 pub mod resolver;
+pub mod resolution;
 pub mod w3c_doc;
 pub mod config;
+pub mod web_resolver;
+pub mod composite_resolver;
+pub mod watch;
+pub mod rpc;
+pub mod quorum;
+pub mod secrets;
+pub mod registrar;
+pub mod verify;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 
-pub use config::initialize_program_id;
-pub use resolver::SolResolver;
+pub use config::{ initialize_program_id, NetworkConfig, SolResolverConfig };
+pub use rpc::RetryPolicy;
+pub use quorum::QuorumPolicy;
+pub use errors::SolResolverError;
+pub use account_source::{ AccountSource, FixtureAccountSource };
+pub use canonical::DocumentDigest;
+pub use credentials::CredentialVerifier;
+pub use did_sol::DidSol;
+pub use resolver::{ ResolveOptions, SolResolver };
+pub use resolution::ResolutionResult;
+pub use watch::DidUpdate;
 pub use w3c_doc::W3cDidDocument;
+pub use web_resolver::WebResolver;
+pub use composite_resolver::CompositeResolver;
+pub use secrets::SolKeypairSecretsResolver;
+pub use registrar::DidRegistrar;
+pub use verify::VerificationRelationship;
@@ -0,0 +1,177 @@
+use crate::errors::*;
+use crate::rpc::RpcEndpointPool;
+
+use std::fmt;
+use log::{ debug, warn };
+use sha2::{ Digest, Sha256 };
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_sdk::pubkey::Pubkey;
+
+/// Agreement required between a network's RPC endpoints before an account read is trusted.
+///
+/// With a quorum configured, the DID account is read from every endpoint of the network
+/// and only accepted when at least `required` of them return the same owner and data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuorumPolicy {
+    /// Number of endpoints that must agree (M of the network's N endpoints)
+    pub required: usize,
+    /// Lowest context slot a response may be read at. Responses are never accepted below
+    /// the highest slot previously agreed on for the network at the same commitment.
+    pub min_context_slot: Option<u64>,
+}
+
+impl QuorumPolicy {
+    pub fn new(required: usize) -> Self {
+        Self {
+            required,
+            min_context_slot: None,
+        }
+    }
+
+    pub fn with_min_context_slot(mut self, min_context_slot: u64) -> Self {
+        self.min_context_slot = Some(min_context_slot);
+        self
+    }
+}
+
+/// What a single endpoint answered during a quorum read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointObservation {
+    pub url: String,
+    /// Context slot of the response, `None` when the call failed
+    pub slot: Option<u64>,
+    /// Owner of the DID account, `None` when the account does not exist or the call failed
+    pub owner: Option<Pubkey>,
+    /// Hex encoded SHA-256 of the account data, `None` when the account does not exist or
+    /// the call failed
+    pub data_hash: Option<String>,
+    pub error: Option<String>,
+}
+
+/// The endpoints of a network did not agree on the content of a DID account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuorumDisagreement {
+    pub network: String,
+    pub account_address: Pubkey,
+    pub required: usize,
+    /// Size of the largest group of endpoints returning the same account
+    pub agreeing: usize,
+    pub observations: Vec<EndpointObservation>,
+}
+
+impl fmt::Display for QuorumDisagreement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} {} endpoints agreed on DID account {}, {} required",
+            self.agreeing,
+            self.observations.len(),
+            self.network,
+            self.account_address,
+            self.required
+        )
+    }
+}
+
+// Owner and data of an account as returned by one endpoint; `None` for a missing account
+pub(crate) type AccountContent = Option<(Pubkey, Vec<u8>)>;
+
+/// Reads `address` from every endpoint of `rpc_pool` and returns the account owner and
+/// data, and the context slot, the quorum agreed on.
+pub(crate) async fn read_account(
+    rpc_pool: &RpcEndpointPool,
+    address: &Pubkey,
+    policy: &QuorumPolicy,
+    config: RpcAccountInfoConfig
+) -> Result<(AccountContent, u64), SolResolverError> {
+    // a floor agreed at one commitment may be ahead of the tip of a stricter one
+    let commitment = config.commitment.unwrap_or_default().commitment;
+    let min_context_slot = policy.min_context_slot
+        .max(config.min_context_slot)
+        .unwrap_or_default()
+        .max(rpc_pool.agreed_slot(commitment));
+    debug!("Quorum read of {} at min context slot {}", address, min_context_slot);
+
+    let config = RpcAccountInfoConfig {
+        min_context_slot: (min_context_slot > 0).then_some(min_context_slot),
+        ..config
+    };
+    let responses = rpc_pool.call_all(|rpc_client| {
+        let config = config.clone();
+        async move { rpc_client.get_account_with_config(address, config).await }
+    }).await;
+
+    let mut observations = Vec::with_capacity(responses.len());
+    // distinct account contents with the slots of the endpoints that returned them
+    let mut groups: Vec<(AccountContent, Vec<u64>)> = vec![];
+
+    for (url, response) in responses {
+        let response = match response {
+            // a node may ignore minContextSlot, so the floor is enforced here as well
+            Ok(response) if response.context.slot < min_context_slot => {
+                Err(format!("response slot {} below minimum {}", response.context.slot, min_context_slot))
+            }
+            Ok(response) => Ok(response),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let response = match response {
+            Ok(response) => response,
+            Err(error) => {
+                observations.push(EndpointObservation {
+                    url,
+                    slot: None,
+                    owner: None,
+                    data_hash: None,
+                    error: Some(error),
+                });
+                continue;
+            }
+        };
+
+        let content = response.value.map(|account| (account.owner, account.data));
+        observations.push(EndpointObservation {
+            url,
+            slot: Some(response.context.slot),
+            owner: content.as_ref().map(|(owner, _)| *owner),
+            data_hash: content.as_ref().map(|(_, data)| hash(data)),
+            error: None,
+        });
+
+        match groups.iter_mut().find(|(existing, _)| *existing == content) {
+            Some((_, slots)) => slots.push(response.context.slot),
+            None => groups.push((content, vec![response.context.slot])),
+        }
+    }
+
+    groups.sort_by_key(|(_, slots)| std::cmp::Reverse(slots.len()));
+    let required = policy.required.max(1);
+    let agreeing = groups.first().map_or(0, |(_, slots)| slots.len());
+
+    // with M <= N/2, two conflicting accounts can both reach M; neither is trusted
+    let contested = groups.get(1).is_some_and(|(_, slots)| slots.len() >= required);
+    if agreeing < required || contested {
+        let disagreement = QuorumDisagreement {
+            network: rpc_pool.network().to_string(),
+            account_address: *address,
+            required,
+            agreeing,
+            observations,
+        };
+        warn!("{}", disagreement);
+        return Err(SolResolverError::QuorumNotReached(disagreement));
+    }
+
+    let (content, slots) = groups.swap_remove(0);
+    let slot = slots.into_iter().max().unwrap_or_default();
+    rpc_pool.record_agreed_slot(commitment, slot);
+
+    Ok((content, slot))
+}
+
+fn hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
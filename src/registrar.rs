@@ -0,0 +1,274 @@
+use crate::config::SolResolverConfig;
+use crate::did_sol::DidSol;
+use crate::errors::*;
+use crate::resolver::derive_did_account;
+
+use anchor_client::anchor_lang::{ AnchorSerialize, InstructionData, ToAccountMetas };
+use log::debug;
+use sol_did::instructions::{ SetControllersArg, UpdateFlagsVerificationMethod };
+use sol_did::state::{ DidAccount, Service, VerificationMethod };
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::signers::Signers;
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+
+pub use crate::constants::{
+    VM_FLAG_ASSERTION,
+    VM_FLAG_AUTHENTICATION,
+    VM_FLAG_CAPABILITY_DELEGATION,
+    VM_FLAG_CAPABILITY_INVOCATION,
+    VM_FLAG_DID_DOC_HIDDEN,
+    VM_FLAG_KEY_AGREEMENT,
+    VM_FLAG_OWNERSHIP_PROOF,
+    VM_FLAG_PROTECTED,
+};
+
+/// Builds sol-did program instructions and transactions that manage a DID account.
+///
+/// Every instruction other than `initialize` is signed by an `authority`: the DID's own key
+/// or any key holding the `VM_FLAG_CAPABILITY_INVOCATION` flag. The DID account does not
+/// grow on its own; `resize` it to `account_size` of the updated account before adding
+/// verification methods, services or controllers.
+#[derive(Debug, Clone)]
+pub struct DidRegistrar {
+    did: DidSol,
+    program_id: Pubkey,
+    did_account: Pubkey,
+}
+
+impl DidRegistrar {
+    /// Creates a registrar for `did` under the given sol-did program.
+    pub fn new(did: DidSol, program_id: Pubkey) -> Self {
+        let (did_account, _) = derive_did_account(&did, &program_id);
+
+        Self {
+            did,
+            program_id,
+            did_account,
+        }
+    }
+
+    /// Creates a registrar using the sol-did program ID configured for the DID's network.
+    pub fn from_config(did: DidSol, config: &SolResolverConfig) -> Self {
+        let program_id = config.network_program_id(did.network());
+        Self::new(did, program_id)
+    }
+
+    pub fn did(&self) -> &DidSol {
+        &self.did
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        self.program_id
+    }
+
+    /// Address of the DID account (PDA) the instructions operate on.
+    pub fn did_account(&self) -> Pubkey {
+        self.did_account
+    }
+
+    /// Creates the DID account with `size` bytes; must be signed by the DID's own key.
+    pub fn initialize(&self, size: u32, payer: &Pubkey) -> Instruction {
+        self.instruction(
+            sol_did::accounts::Initialize {
+                did_data: self.did_account,
+                authority: self.did.pubkey(),
+                payer: *payer,
+                system_program: system_program::ID,
+            },
+            sol_did::instruction::Initialize { size }
+        )
+    }
+
+    /// Reallocates the DID account to `size` bytes, `payer` covering the rent difference.
+    pub fn resize(&self, size: u32, authority: &Pubkey, payer: &Pubkey) -> Instruction {
+        self.instruction(
+            sol_did::accounts::Resize {
+                did_data: self.did_account,
+                authority: *authority,
+                payer: *payer,
+                system_program: system_program::ID,
+            },
+            sol_did::instruction::Resize {
+                size,
+                eth_signature: None,
+            }
+        )
+    }
+
+    /// Adds a verification method with the given `VM_FLAG_*` flags.
+    pub fn add_verification_method(
+        &self,
+        authority: &Pubkey,
+        fragment: &str,
+        method_type: u8,
+        key_data: Vec<u8>,
+        flags: u16
+    ) -> Instruction {
+        self.instruction(
+            sol_did::accounts::AddVerificationMethod {
+                did_data: self.did_account,
+                authority: *authority,
+            },
+            sol_did::instruction::AddVerificationMethod {
+                verification_method: VerificationMethod {
+                    fragment: fragment.to_string(),
+                    flags,
+                    method_type,
+                    key_data,
+                },
+                eth_signature: None,
+            }
+        )
+    }
+
+    pub fn remove_verification_method(&self, authority: &Pubkey, fragment: &str) -> Instruction {
+        self.instruction(
+            sol_did::accounts::RemoveVerificationMethod {
+                did_data: self.did_account,
+                authority: *authority,
+            },
+            sol_did::instruction::RemoveVerificationMethod {
+                fragment: fragment.to_string(),
+                eth_signature: None,
+            }
+        )
+    }
+
+    /// Replaces the flags of a verification method, including `#default`.
+    pub fn set_vm_flags(&self, authority: &Pubkey, fragment: &str, flags: u16) -> Instruction {
+        self.instruction(
+            sol_did::accounts::SetVmFlagsVerificationMethod {
+                did_data: self.did_account,
+                authority: *authority,
+            },
+            sol_did::instruction::SetVmFlags {
+                flags_vm: UpdateFlagsVerificationMethod {
+                    fragment: fragment.to_string(),
+                    flags,
+                },
+                eth_signature: None,
+            }
+        )
+    }
+
+    /// Adds a service; with `allow_overwrite` an existing service with the same fragment
+    /// is replaced instead of failing the transaction.
+    pub fn add_service(
+        &self,
+        authority: &Pubkey,
+        fragment: &str,
+        service_type: &str,
+        service_endpoint: &str,
+        allow_overwrite: bool
+    ) -> Instruction {
+        self.instruction(
+            sol_did::accounts::AddService {
+                did_data: self.did_account,
+                authority: *authority,
+            },
+            sol_did::instruction::AddService {
+                service: Service {
+                    fragment: fragment.to_string(),
+                    service_type: service_type.to_string(),
+                    service_endpoint: service_endpoint.to_string(),
+                },
+                allow_overwrite,
+                eth_signature: None,
+            }
+        )
+    }
+
+    pub fn remove_service(&self, authority: &Pubkey, fragment: &str) -> Instruction {
+        self.instruction(
+            sol_did::accounts::RemoveService {
+                did_data: self.did_account,
+                authority: *authority,
+            },
+            sol_did::instruction::RemoveService {
+                fragment: fragment.to_string(),
+                eth_signature: None,
+            }
+        )
+    }
+
+    /// Replaces the DID's controllers: did:sol keys and DIDs of other methods.
+    pub fn set_controllers(
+        &self,
+        authority: &Pubkey,
+        native_controllers: Vec<Pubkey>,
+        other_controllers: Vec<String>
+    ) -> Instruction {
+        self.instruction(
+            sol_did::accounts::SetControllers {
+                did_data: self.did_account,
+                authority: *authority,
+            },
+            sol_did::instruction::SetControllers {
+                set_controllers_arg: SetControllersArg {
+                    native_controllers,
+                    other_controllers,
+                },
+                eth_signature: None,
+            }
+        )
+    }
+
+    /// Closes the DID account, sending its lamports to `destination`; the DID reverts to
+    /// its generative document.
+    pub fn close(&self, authority: &Pubkey, destination: &Pubkey) -> Instruction {
+        self.instruction(
+            sol_did::accounts::Close {
+                did_data: self.did_account,
+                authority: *authority,
+                destination: *destination,
+                system_program: system_program::ID,
+            },
+            sol_did::instruction::Close { eth_signature: None }
+        )
+    }
+
+    /// Wraps instructions into an unsigned transaction paid for by `payer`.
+    pub fn transaction(&self, instructions: &[Instruction], payer: &Pubkey) -> Transaction {
+        Transaction::new_with_payer(instructions, Some(payer))
+    }
+
+    /// Wraps instructions into a transaction paid for by `payer` and signed by `signers`,
+    /// which must include the payer and every authority.
+    pub fn signed_transaction<T: Signers + ?Sized>(
+        &self,
+        instructions: &[Instruction],
+        payer: &Pubkey,
+        signers: &T,
+        recent_blockhash: Hash
+    ) -> Result<Transaction, SolResolverError> {
+        let mut transaction = self.transaction(instructions, payer);
+        transaction
+            .try_sign(signers, recent_blockhash)
+            .map_err(|e| SolResolverError::SigningError(e.to_string()))?;
+
+        Ok(transaction)
+    }
+
+    fn instruction(&self, accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        };
+        debug!("Built sol-did instruction for {} ({} bytes)", self.did, instruction.data.len());
+
+        instruction
+    }
+}
+
+/// Size in bytes a DID account needs to hold `did_account`, to pass to `resize` after
+/// adding verification methods, services or controllers to a copy of the on-chain account.
+pub fn account_size(did_account: &DidAccount) -> u32 {
+    let data = did_account.try_to_vec().expect("Serializing into a Vec cannot fail");
+
+    // account discriminator followed by the serialized account
+    (8 + data.len()) as u32
+}
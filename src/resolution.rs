@@ -0,0 +1,156 @@
+use crate::errors::SolResolverError;
+use crate::resolver::SolResolution;
+use crate::w3c_doc::{ self, W3cDidDocument };
+
+use serde::Serialize;
+
+pub const DID_RESOLUTION_CONTEXT: &str = "https://w3id.org/did-resolution/v1";
+/// Media type of a bare DID document
+pub const DID_LD_JSON: &str = "application/did+ld+json";
+/// Media type of a bare DID document in CBOR
+pub const DID_CBOR: &str = "application/did+cbor";
+/// Media type of a full DID Resolution Result
+pub const DID_RESOLUTION_LD_JSON: &str =
+    "application/ld+json;profile=\"https://w3id.org/did-resolution\"";
+
+/// DID Resolution error codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ResolutionError {
+    InvalidDid,
+    NotFound,
+    RepresentationNotSupported,
+    MethodNotSupported,
+    InternalError,
+}
+
+impl From<&SolResolverError> for ResolutionError {
+    fn from(err: &SolResolverError) -> Self {
+        match err {
+            SolResolverError::InvalidDid(_) => ResolutionError::InvalidDid,
+            SolResolverError::UnsupportedDidMethod(_) | SolResolverError::UnsupportedNetwork(_) =>
+                ResolutionError::MethodNotSupported,
+            SolResolverError::NotFound | SolResolverError::DidUrlNotFound(_) =>
+                ResolutionError::NotFound,
+            _ => ResolutionError::InternalError,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResolutionError>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentMetadata {
+    /// Set when every verification method of the DID is hidden. sol-did itself has no
+    /// deactivated state; this is the resolver's convention for an unusable document.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub deactivated: bool,
+    /// Slot the DID account was read at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    /// Hex encoded SHA-256 of the DID account data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_hash: Option<String>,
+    /// Layout and version of the DID account, `None` for chainless DIDs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_version: Option<DidAccountVersion>,
+}
+
+/// Layout a DID account was decoded from, e.g. `{"layout": "didAccount", "version": 0}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "layout", rename_all = "camelCase")]
+pub enum DidAccountVersion {
+    /// Anchor `DidAccount` of the current sol-did program, with its version field
+    DidAccount {
+        version: u8,
+    },
+    /// Pre-Anchor `SolData` account of the legacy sol-did program, with its version string
+    Legacy {
+        version: String,
+    },
+}
+
+/// Resource a DID URL dereferences to
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum DereferencedResource {
+    DidDocument(W3cDidDocument),
+    VerificationMethod(w3c_doc::VerificationMethod),
+    Service(w3c_doc::Service),
+}
+
+/// A DID Resolution Result as defined by the DID Resolution specification.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolutionResult {
+    #[serde(rename = "@context")]
+    pub context: String,
+    pub did_document: Option<W3cDidDocument>,
+    pub did_resolution_metadata: ResolutionMetadata,
+    pub did_document_metadata: DocumentMetadata,
+}
+
+impl ResolutionResult {
+    /// Builds a resolution result for a successfully resolved document.
+    pub fn success(resolution: SolResolution, content_type: &str) -> Self {
+        Self {
+            context: DID_RESOLUTION_CONTEXT.to_string(),
+            did_document: Some(resolution.w3c_doc),
+            did_resolution_metadata: ResolutionMetadata {
+                content_type: Some(content_type.to_string()),
+                ..Default::default()
+            },
+            did_document_metadata: resolution.metadata,
+        }
+    }
+
+    /// Builds a resolution result describing a failed resolution.
+    pub fn error(error: ResolutionError, message: &str) -> Self {
+        Self {
+            context: DID_RESOLUTION_CONTEXT.to_string(),
+            did_document: None,
+            did_resolution_metadata: ResolutionMetadata {
+                error: Some(error),
+                error_message: Some(message.to_string()),
+                ..Default::default()
+            },
+            did_document_metadata: DocumentMetadata::default(),
+        }
+    }
+
+    /// Builds a resolution result from the outcome of `SolResolver::resolve_document`.
+    pub fn from_resolution(
+        resolution: Result<SolResolution, SolResolverError>,
+        content_type: &str
+    ) -> Self {
+        match resolution {
+            Ok(resolution) => Self::success(resolution, content_type),
+            Err(e) => Self::error(ResolutionError::from(&e), &e.to_string()),
+        }
+    }
+
+    /// The DID document in the representation named by the resolution metadata's content
+    /// type: CBOR for `application/did+cbor`, JSON otherwise. `None` when resolution failed.
+    pub fn document_stream(&self) -> Option<Vec<u8>> {
+        let did_document = self.did_document.as_ref()?;
+
+        Some(match self.did_resolution_metadata.content_type.as_deref() {
+            Some(DID_CBOR) => did_document.to_cbor(),
+            _ => serde_json::to_vec(did_document).expect("Serializing a document cannot fail"),
+        })
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.did_document_metadata.deactivated
+    }
+}
@@ -1,117 +1,582 @@
+use crate::account_source::AccountSource;
+use crate::cache::DocumentCache;
+use crate::config::SolResolverConfig;
 use crate::constants::*;
+use crate::did_sol::DidSol;
 use crate::errors::*;
+use crate::legacy::{ derive_legacy_account, legacy_program_id, SolData };
+use crate::quorum::{ self, AccountContent };
 use crate::did_doc_builder::*;
+use crate::resolution::{ DereferencedResource, DidAccountVersion, DocumentMetadata };
+use crate::rpc::{ EndpointHealth, RpcEndpointPool };
+use crate::w3c_doc::W3cDidDocument;
 
-use std::str::FromStr;
-use anchor_client::{ anchor_lang::AccountDeserialize, solana_client::rpc_client::RpcClient };
-use didcomm::{ did::{ DIDDoc, DIDResolver }, error::Error, error::ErrorKind };
-use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use anchor_client::anchor_lang::{ AccountDeserialize, Discriminator };
+use didcomm::did::{ DIDDoc, DIDResolver };
 use sol_did::state::DidAccount;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::client_error::ClientError;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_client::rpc_request::{ RpcError, RpcResponseErrorData };
 use async_trait::async_trait;
 use log::{ info, debug };
+use sha2::{ Digest, Sha256 };
+use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction::MAX_PERMITTED_DATA_LENGTH;
 
 // Resolver struct
-pub struct SolResolver {}
+#[derive(Clone)]
+pub struct SolResolver {
+    config: SolResolverConfig,
+    pub(crate) cache: Arc<DocumentCache>,
+    // RPC endpoints per network; shared so endpoint health outlives individual calls
+    pools: Arc<BTreeMap<String, RpcEndpointPool>>,
+    // Reads DID accounts instead of the RPC endpoints when set
+    account_source: Option<Arc<dyn AccountSource>>,
+}
 
 impl Default for SolResolver {
     fn default() -> Self {
-        Self {}
+        Self::new(SolResolverConfig::default())
     }
 }
 
-impl SolResolver {
-    // Function to derive the DID account address
-    fn derive_did_account(did_pubkey: &Pubkey) -> (Pubkey, u8) {
-        let seed = "did-account";
+/// A resolved did:sol document together with its document metadata.
+#[derive(Debug, Clone)]
+pub struct SolResolution {
+    pub did_doc: DIDDoc,
+    /// The same document in the W3C representation of the sol-did client
+    pub w3c_doc: W3cDidDocument,
+    pub metadata: DocumentMetadata,
+}
 
-        let program_id = Pubkey::from_str("didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc").unwrap();
+/// On-chain state of a did:sol identifier as observed at a given slot.
+#[derive(Debug, Clone)]
+pub struct DidAccountSnapshot {
+    pub did: String,
+    pub did_pubkey: Pubkey,
+    /// Address the account was read from: the DID account, or the legacy `SolData` account
+    /// found by legacy fallback
+    pub account_address: Pubkey,
+    /// Raw DID account data, `None` when the DID has no on-chain account
+    pub data: Option<Vec<u8>>,
+    /// Program owning the DID account, `None` when the DID has no on-chain account
+    pub owner: Option<Pubkey>,
+    /// Slot of the RPC context the account was read at
+    pub slot: u64,
+}
 
-        Pubkey::find_program_address(&[seed.as_bytes(), &did_pubkey.as_ref()], &program_id)
+/// Per-call overrides of the resolver's commitment and minimum context slot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResolveOptions {
+    /// Commitment level of the read, instead of `SolResolverConfig::commitment`
+    pub commitment: Option<CommitmentConfig>,
+    /// Lowest slot the account may be read at, e.g. the slot a DID update landed in
+    pub min_context_slot: Option<u64>,
+}
+
+impl ResolveOptions {
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = Some(commitment);
+        self
+    }
+
+    pub fn with_min_context_slot(mut self, min_context_slot: u64) -> Self {
+        self.min_context_slot = Some(min_context_slot);
+        self
     }
 }
 
-#[async_trait(?Send)]
-impl DIDResolver for SolResolver {
-    async fn resolve(
+impl DidAccountSnapshot {
+    /// Hex encoded SHA-256 of the account data (of no bytes for chainless DIDs).
+    pub fn data_hash(&self) -> String {
+        Sha256::digest(self.data.as_deref().unwrap_or_default())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+}
+
+impl SolResolver {
+    pub fn new(config: SolResolverConfig) -> Self {
+        let cache = Arc::new(DocumentCache::new(config.cache_ttl, config.cache_capacity));
+        let pools = config.networks
+            .iter()
+            .map(|(name, network)| {
+                let pool = RpcEndpointPool::new(
+                    name,
+                    network,
+                    config.timeout,
+                    config.commitment,
+                    config.retry_policy.clone()
+                );
+                (name.clone(), pool)
+            })
+            .collect();
+
+        Self {
+            config,
+            cache,
+            pools: Arc::new(pools),
+            account_source: None,
+        }
+    }
+
+    /// Reads DID accounts from `source` instead of the networks' RPC endpoints. DIDs must
+    /// still name a configured network, whose program ID is used for PDA derivation.
+    pub fn with_account_source(mut self, source: impl AccountSource + 'static) -> Self {
+        self.account_source = Some(Arc::new(source));
+        self
+    }
+
+    /// Whether DID accounts are read from an `AccountSource` instead of RPC.
+    pub(crate) fn has_account_source(&self) -> bool {
+        self.account_source.is_some()
+    }
+
+    pub fn config(&self) -> &SolResolverConfig {
+        &self.config
+    }
+
+    /// Derives the DID account address (PDA) and bump of a DID, using the sol-did program
+    /// ID of the DID's network.
+    pub fn derive_did_account(&self, did: &DidSol) -> (Pubkey, u8) {
+        derive_did_account(did, &self.config.network_program_id(did.network()))
+    }
+
+    /// Resolves a did:sol identifier into its DIDComm document and document metadata.
+    ///
+    /// DIDs without an on-chain account resolve to their generative document unless the
+    /// resolver is configured as strict, in which case `SolResolverError::NotFound` is returned.
+    pub async fn resolve_document(&self, did: &str) -> Result<SolResolution, SolResolverError> {
+        self.resolve_document_with(did, &ResolveOptions::default()).await
+    }
+
+    /// Resolves a did:sol identifier with per-call commitment and minimum context slot.
+    ///
+    /// The slot the account was read at is returned in the document metadata, so a caller
+    /// that just updated a DID can pass the update's slot as `min_context_slot` to read its
+    /// own write.
+    pub async fn resolve_document_with(
         &self,
-        did: &str
-    ) -> std::result::Result<Option<DIDDoc>, didcomm::error::Error> {
-        info!("Resolving DID: {}", did);
+        did: &str,
+        options: &ResolveOptions
+    ) -> Result<SolResolution, SolResolverError> {
+        // cached documents were read at the configured commitment
+        let cacheable = options.commitment.is_none();
+        if cacheable {
+            if let Some(resolution) = self.cache.get(did) {
+                if resolution.metadata.slot >= options.min_context_slot {
+                    return Ok(resolution);
+                }
+            }
+        }
 
-        // Validate DID format using regex
-        let re = Regex::new(DID_SOL_REGEX).map_err(|_|
-            Error::msg(ErrorKind::Malformed, SolResolverError::InvalidDidFormat.to_string())
-        )?;
+        let snapshot = self.fetch_account_with(did, options).await?;
+        let resolution = self.build_document(&snapshot)?;
+        if cacheable {
+            self.cache.insert(did, &resolution);
+        }
 
-        let captures = re
-            .captures(did)
-            .ok_or_else(||
-                Error::msg(ErrorKind::Malformed, SolResolverError::InvalidDidFormat.to_string())
-            )?;
-
-        // Extract optional network and address
-        let network = captures
-            .get(1)
-            .map(|m| m.as_str())
-            .unwrap_or("mainnet");
-
-        let address = captures
-            .get(2)
-            .ok_or_else(||
-                Error::msg(ErrorKind::Malformed, SolResolverError::InvalidSolanaAddress.to_string())
-            )?
-            .as_str();
-
-        // Check if the address is valid before converting
-        if address.len() < 32 || address.len() > 44 {
-            return Err(
-                Error::msg(ErrorKind::Malformed, "Invalid Solana address length".to_string())
-            );
+        Ok(resolution)
+    }
+
+    /// Resolves a did:sol identifier into its W3C document.
+    ///
+    /// The document is built from the same account read (or cached resolution) as the
+    /// DIDComm document of `resolve_document`, so both views of a DID always agree.
+    /// Deactivated DIDs fail with `SolResolverError::Deactivated`.
+    pub async fn resolve_w3c(&self, did: &str) -> Result<W3cDidDocument, SolResolverError> {
+        self.resolve_w3c_with(did, &ResolveOptions::default()).await
+    }
+
+    /// Resolves a did:sol identifier into its W3C document with per-call commitment and
+    /// minimum context slot.
+    pub async fn resolve_w3c_with(
+        &self,
+        did: &str,
+        options: &ResolveOptions
+    ) -> Result<W3cDidDocument, SolResolverError> {
+        let resolution = self.resolve_document_with(did, options).await?;
+        if resolution.metadata.deactivated {
+            return Err(SolResolverError::Deactivated);
         }
 
-        debug!("Extracted Network: {}", network);
-        debug!("Extracted Address: {}", address);
+        Ok(resolution.w3c_doc)
+    }
 
-        // Determine the appropriate RPC URL based on network
-        let rpc_url = match network {
-            "testnet" => TESTNET_RPC,
-            "devnet" => DEVNET_RPC,
-            "localnet" => LOCALNET_RPC,
-            _ => MAINNET_RPC, // Default to mainnet if no network is specified
-        };
+    /// Drops the cached document for `did`, if any.
+    pub fn invalidate(&self, did: &str) {
+        self.cache.invalidate(did);
+    }
 
-        let rpc_client = RpcClient::new(rpc_url.to_string());
+    /// Reads the DID account of a did:sol identifier without building its document.
+    pub async fn fetch_account(&self, did: &str) -> Result<DidAccountSnapshot, SolResolverError> {
+        self.fetch_account_with(did, &ResolveOptions::default()).await
+    }
 
-        // Convert extracted address to a Solana Pubkey
-        let did_pubkey = Pubkey::from_str(address).map_err(|_m|
-            Error::msg(ErrorKind::Malformed, SolResolverError::InvalidSolanaAddress.to_string())
-        )?;
+    /// Reads the DID account of a did:sol identifier with per-call commitment and minimum
+    /// context slot.
+    ///
+    /// With `SolResolverConfig::resolve_legacy` set, a DID without a `DidAccount` is looked
+    /// up in the legacy sol-did program and the snapshot holds the legacy account, if any.
+    pub async fn fetch_account_with(
+        &self,
+        did: &str,
+        options: &ResolveOptions
+    ) -> Result<DidAccountSnapshot, SolResolverError> {
+        info!("Resolving DID: {}", did);
+
+        let did_sol: DidSol = did.parse()?;
+        let did_pubkey = did_sol.pubkey();
         debug!("Derived Solana Pubkey: {:?}", did_pubkey);
 
         // Derive the DID account
-        let (did_account_pubkey, _) = Self::derive_did_account(&did_pubkey);
+        let (did_account_pubkey, _) = self.derive_did_account(&did_sol);
         debug!("Derived DID Account Pubkey: {:?}", did_account_pubkey);
 
-        // After deriving did_account_pubkey
-        let account_data_result = rpc_client.get_account_data(&did_account_pubkey);
-        debug!("Account Data Result: {:?}", account_data_result);
-
-        let did_document = match account_data_result {
-            Ok(account_data) => {
-                let did_account = DidAccount::try_deserialize(&mut account_data.as_ref()).map_err(
-                    |m|
-                        Error::msg(
-                            ErrorKind::Malformed,
-                            format!("Anchor Deserialization Error: {}", m)
+        let (mut account, mut slot) = self.read_account(
+            did_sol.network(),
+            &did_account_pubkey,
+            options
+        ).await?;
+        let mut account_address = did_account_pubkey;
+
+        if account.is_none() && self.config.resolve_legacy {
+            let legacy_pubkey = derive_legacy_account(&did_pubkey);
+            debug!("No DID account, trying legacy account {:?}", legacy_pubkey);
+
+            (account, slot) = self.read_account(did_sol.network(), &legacy_pubkey, options).await?;
+            if account.is_some() {
+                account_address = legacy_pubkey;
+            }
+        }
+
+        Ok(DidAccountSnapshot {
+            did: did.to_string(),
+            did_pubkey,
+            account_address,
+            owner: account.as_ref().map(|(owner, _)| *owner),
+            data: account.map(|(_, data)| data),
+            slot,
+        })
+    }
+
+    // Reads an account of `network` through the account source, the quorum or the RPC pool,
+    // returning its owner and data along with the slot it was read at
+    async fn read_account(
+        &self,
+        network: &str,
+        address: &Pubkey,
+        options: &ResolveOptions
+    ) -> Result<(AccountContent, u64), SolResolverError> {
+        let rpc_pool = self.rpc_pool(network)?;
+        let config = self.account_info_config(options);
+        let min_context_slot = config.min_context_slot;
+        let address = *address;
+
+        match (&self.account_source, &self.config.quorum) {
+            (Some(source), _) => {
+                let commitment = options.commitment.unwrap_or(self.config.commitment);
+                let (account, slot) = source.get_account(&address, commitment).await?;
+                if let Some(min_slot) = min_context_slot.filter(|min_slot| slot < *min_slot) {
+                    return Err(
+                        SolResolverError::RpcError(
+                            min_context_slot_not_reached(slot, min_slot).to_string()
                         )
-                )?;
+                    );
+                }
+
+                Ok((account.map(|account| (account.owner, account.data)), slot))
+            }
+            (None, Some(policy)) => quorum::read_account(rpc_pool, &address, policy, config).await,
+            (None, None) => {
+                let response = rpc_pool.call(|rpc_client| {
+                    let config = config.clone();
+                    async move {
+                        let response = rpc_client.get_account_with_config(&address, config).await?;
+                        match min_context_slot {
+                            // a node ignoring minContextSlot is treated like one rejecting it,
+                            // so the call moves on to the next endpoint
+                            Some(min_slot) if response.context.slot < min_slot => {
+                                Err(min_context_slot_not_reached(response.context.slot, min_slot))
+                            }
+                            _ => Ok(response),
+                        }
+                    }
+                }).await?;
+                debug!("Account Result: {:?}", response);
+
+                Ok((response.value.map(|account| (account.owner, account.data)), response.context.slot))
+            }
+        }
+    }
+
+    /// Dereferences a DID URL into the document, or the verification method or service
+    /// named by its fragment.
+    pub async fn dereference(
+        &self,
+        did_url: &str
+    ) -> Result<DereferencedResource, SolResolverError> {
+        let (did, fragment) = match did_url.split_once('#') {
+            Some((did, fragment)) => (did, Some(fragment)),
+            None => (did_url, None),
+        };
+
+        let resolution = self.resolve_document(did).await?;
+        let document = resolution.w3c_doc;
+
+        let fragment = match fragment {
+            Some(fragment) => fragment,
+            None => {
+                return Ok(DereferencedResource::DidDocument(document));
+            }
+        };
+        let id = format!("{}#{}", did, fragment);
+
+        if let Some(vm) = document.verification_method.into_iter().find(|vm| vm.id == id) {
+            return Ok(DereferencedResource::VerificationMethod(vm));
+        }
+
+        document.service
+            .into_iter()
+            .find(|service| service.id == id)
+            .map(DereferencedResource::Service)
+            .ok_or(SolResolverError::DidUrlNotFound(did_url.to_string()))
+    }
+
+    /// Builds the DIDComm document for a previously fetched DID account.
+    pub fn build_document(
+        &self,
+        snapshot: &DidAccountSnapshot
+    ) -> Result<SolResolution, SolResolverError> {
+        let did = snapshot.did.as_str();
+        let address = snapshot.did_pubkey.to_string();
+        let mut metadata = DocumentMetadata {
+            slot: Some(snapshot.slot),
+            data_hash: Some(snapshot.data_hash()),
+            ..Default::default()
+        };
+
+        let did_sol: DidSol = did.parse()?;
+
+        let did_account = self.decode_versioned_account(snapshot)?.map(|(did_account, version)| {
+            metadata.account_version = Some(version);
+            did_account
+        });
+        let builder = match &did_account {
+            Some(did_account) => {
+                metadata.deactivated = Self::is_deactivated(did_account);
+
+                DidDocBuilder::new(did, &address).with_onchain_data(did_account, did)
+            }
+            None if self.config.strict => {
+                return Err(SolResolverError::NotFound);
+            }
+            None => DidDocBuilder::new(did, &address),
+        };
+        let did_doc = if self.config.derive_key_agreement {
+            builder.with_derived_key_agreement().build()
+        } else {
+            builder.build()
+        };
+        let w3c_doc = W3cDidDocument::from_did_account(&did_sol, did_account.as_ref());
+
+        Ok(SolResolution { did_doc, w3c_doc, metadata })
+    }
 
-                DidDocBuilder::new(did, address).with_onchain_data(&did_account, did).build()
+    // Document of a DID as if it had no on-chain account
+    fn generative_document(&self, did: &str) -> Result<SolResolution, SolResolverError> {
+        let did_sol: DidSol = did.parse()?;
+        let (account_address, _) = self.derive_did_account(&did_sol);
+        self.build_document(
+            &(DidAccountSnapshot {
+                did: did.to_string(),
+                did_pubkey: did_sol.pubkey(),
+                account_address,
+                data: None,
+                owner: None,
+                slot: 0,
+            })
+        )
+    }
+
+    /// Validates and decodes the DID account of a snapshot, `None` for chainless DIDs.
+    ///
+    /// The account must be owned by the configured sol-did program, start with the
+    /// `DidAccount` discriminator, fit the account size bounds and store the bump derived
+    /// for the DID, so a spoofed or mis-owned account is never turned into a DID document.
+    /// Accounts of the legacy sol-did program are mapped onto `DidAccount`, see
+    /// `decode_versioned_account`.
+    pub fn decode_account(
+        &self,
+        snapshot: &DidAccountSnapshot
+    ) -> Result<Option<DidAccount>, SolResolverError> {
+        Ok(self.decode_versioned_account(snapshot)?.map(|(did_account, _)| did_account))
+    }
+
+    /// Like `decode_account`, also returning the layout the account was decoded from.
+    ///
+    /// Accounts owned by the legacy (pre-Anchor) sol-did program have no discriminator;
+    /// they are decoded as `SolData`, must name the DID key as their authority and are
+    /// mapped onto the current `DidAccount` model.
+    pub fn decode_versioned_account(
+        &self,
+        snapshot: &DidAccountSnapshot
+    ) -> Result<Option<(DidAccount, DidAccountVersion)>, SolResolverError> {
+        let data = match &snapshot.data {
+            Some(data) => data,
+            None => {
+                return Ok(None);
             }
-            Err(_) => { DidDocBuilder::new(did, address).build() }
         };
 
-        Ok(Some(did_document))
+        let did_sol: DidSol = snapshot.did.parse()?;
+        let program_id = self.config.network_program_id(did_sol.network());
+        match snapshot.owner {
+            Some(owner) if owner == program_id => {}
+            Some(owner) if owner == legacy_program_id() => {
+                return Self::decode_legacy_account(&did_sol, snapshot.account_address, data).map(Some);
+            }
+            owner => {
+                return Err(SolResolverError::InvalidAccountOwner(owner.unwrap_or_default()));
+            }
+        }
+
+        if data.len() < DID_ACCOUNT_MIN_SIZE || data.len() > MAX_PERMITTED_DATA_LENGTH as usize {
+            return Err(SolResolverError::InvalidAccountSize(data.len()));
+        }
+        if data[..8] != DidAccount::DISCRIMINATOR {
+            return Err(SolResolverError::InvalidAccountDiscriminator);
+        }
+
+        let did_account = DidAccount::try_deserialize(&mut data.as_slice()).map_err(|m|
+            SolResolverError::DeserializationError(m.to_string())
+        )?;
+
+        let (_, bump) = self.derive_did_account(&did_sol);
+        if did_account.bump != bump {
+            return Err(SolResolverError::BumpMismatch {
+                expected: bump,
+                found: did_account.bump,
+            });
+        }
+
+        let version = DidAccountVersion::DidAccount { version: did_account.version };
+        Ok(Some((did_account, version)))
+    }
+
+    /// Health of every configured RPC endpoint, for monitoring the retry policy.
+    pub fn endpoint_health(&self) -> Vec<EndpointHealth> {
+        self.pools
+            .values()
+            .flat_map(|pool| pool.health())
+            .collect()
+    }
+
+    // Account read settings with the per-call overrides applied
+    pub(crate) fn account_info_config(&self, options: &ResolveOptions) -> RpcAccountInfoConfig {
+        RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(options.commitment.unwrap_or(self.config.commitment)),
+            data_slice: None,
+            min_context_slot: options.min_context_slot.or(self.config.min_context_slot),
+        }
+    }
+
+    // Function to look up the RPC endpoints of a configured network
+    pub(crate) fn rpc_pool(&self, network: &str) -> Result<&RpcEndpointPool, SolResolverError> {
+        self.pools.get(network).ok_or_else(|| SolResolverError::UnsupportedNetwork(network.to_string()))
+    }
+
+    // Decodes a legacy `SolData` account into the current model
+    fn decode_legacy_account(
+        did_sol: &DidSol,
+        account_address: Pubkey,
+        data: &[u8]
+    ) -> Result<(DidAccount, DidAccountVersion), SolResolverError> {
+        if data.len() > MAX_PERMITTED_DATA_LENGTH as usize {
+            return Err(SolResolverError::InvalidAccountSize(data.len()));
+        }
+        // a SolData account is only the DID's when it lives at the DID's legacy PDA
+        let legacy_address = derive_legacy_account(&did_sol.pubkey());
+        if account_address != legacy_address {
+            return Err(
+                SolResolverError::InvalidLegacyAccount(
+                    format!("Account {} is not the legacy account {}", account_address, legacy_address)
+                )
+            );
+        }
+
+        let sol_data = SolData::decode(data)?;
+        if sol_data.authority != did_sol.pubkey() {
+            return Err(
+                SolResolverError::InvalidLegacyAccount(
+                    format!("Authority {} is not the DID key", sol_data.authority)
+                )
+            );
+        }
+        let version = DidAccountVersion::Legacy { version: sol_data.version.clone() };
+
+        Ok((sol_data.into_did_account(did_sol.network())?, version))
+    }
+
+    // sol-did has no deactivation instruction; by this resolver's own convention, a DID is
+    // reported deactivated once every verification method, including the default key, has
+    // been hidden from the DID document, since such a document can no longer be used
+    fn is_deactivated(did_account: &DidAccount) -> bool {
+        did_account.initial_verification_method.flags & VM_FLAG_DID_DOC_HIDDEN != 0 &&
+            did_account.verification_methods
+                .iter()
+                .all(|vm| vm.flags & VM_FLAG_DID_DOC_HIDDEN != 0)
+    }
+}
+
+// Derives the DID account address (PDA) and bump of a DID under `program_id`
+pub(crate) fn derive_did_account(did: &DidSol, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DID_ACCOUNT_SEED.as_bytes(), did.pubkey().as_ref()], program_id)
+}
+
+// Error a node answers with when it has not reached the requested minContextSlot
+fn min_context_slot_not_reached(context_slot: u64, min_context_slot: u64) -> ClientError {
+    ClientError::from(RpcError::RpcResponseError {
+        code: JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
+        message: format!(
+            "Minimum context slot has not been reached: {} < {}",
+            context_slot,
+            min_context_slot
+        ),
+        data: RpcResponseErrorData::Empty,
+    })
+}
+
+#[async_trait(?Send)]
+impl DIDResolver for SolResolver {
+    /// Resolves a did:sol DID for DIDComm.
+    ///
+    /// As DIDComm expects of a did:sol resolver, a DID whose account cannot be read (RPC
+    /// failures, no quorum) resolves to its generative document and deactivated DIDs to
+    /// their document. Strict resolvers report both instead, as errors.
+    async fn resolve(
+        &self,
+        did: &str
+    ) -> std::result::Result<Option<DIDDoc>, didcomm::error::Error> {
+        match self.resolve_document(did).await {
+            Ok(resolution) if resolution.metadata.deactivated && self.config.strict => {
+                Err(SolResolverError::Deactivated.into())
+            }
+            Ok(resolution) => Ok(Some(resolution.did_doc)),
+            Err(SolResolverError::NotFound) => Ok(None),
+            Err(SolResolverError::RpcError(_) | SolResolverError::QuorumNotReached(_)) if
+                !self.config.strict
+            => {
+                info!("Could not read the account of {}, resolving its generative document", did);
+                Ok(Some(self.generative_document(did)?.did_doc))
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 }
@@ -0,0 +1,318 @@
+use crate::config::NetworkConfig;
+use crate::errors::*;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{ Arc, Mutex };
+use std::time::{ Duration, Instant };
+use futures::future::join_all;
+use log::{ debug, warn };
+use rand::Rng;
+use solana_client::client_error::{ ClientError, ClientErrorKind };
+use reqwest::header::{ HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE };
+use solana_client::http_sender::HttpSender;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::RpcClientConfig;
+use solana_client::rpc_request::RpcError;
+use solana_sdk::commitment_config::{ CommitmentConfig, CommitmentLevel };
+
+/// Retry and failover behaviour for RPC calls.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts per call, spread across the network's endpoints
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for every further retry
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Fraction of each delay that is randomized, between 0.0 and 1.0
+    pub jitter: f64,
+    /// Consecutive failures after which an endpoint is ejected
+    pub eject_after: u32,
+    /// How long an ejected endpoint is skipped
+    pub eject_duration: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            jitter: 0.5,
+            eject_after: 3,
+            eject_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (starting at 0), with jitter applied.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let base = self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+
+        base.mul_f64(1.0 - jitter * rand::thread_rng().gen::<f64>())
+    }
+}
+
+/// Health of a single RPC endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointHealth {
+    pub network: String,
+    pub url: String,
+    pub consecutive_failures: u32,
+    pub total_failures: u64,
+    pub total_successes: u64,
+    /// Whether the endpoint is currently skipped after repeated failures
+    pub ejected: bool,
+}
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    consecutive_failures: u32,
+    total_failures: u64,
+    total_successes: u64,
+    ejected_until: Option<Instant>,
+}
+
+struct Endpoint {
+    url: String,
+    client: Arc<RpcClient>,
+    state: Mutex<EndpointState>,
+}
+
+/// Ordered RPC endpoints of one network, called with retry, backoff and failover.
+pub(crate) struct RpcEndpointPool {
+    network: String,
+    endpoints: Vec<Endpoint>,
+    policy: RetryPolicy,
+    // highest context slot a quorum read agreed on at each commitment; later quorum reads
+    // at that commitment may not go below it
+    agreed_slots: Mutex<HashMap<CommitmentLevel, u64>>,
+}
+
+impl RpcEndpointPool {
+    pub fn new(
+        network: &str,
+        config: &NetworkConfig,
+        timeout: Duration,
+        commitment: CommitmentConfig,
+        policy: RetryPolicy
+    ) -> Self {
+        let http_client = Self::http_client(network, config, timeout);
+        let endpoints = config.rpc_urls
+            .iter()
+            .map(|url| Endpoint {
+                url: url.clone(),
+                client: Arc::new(
+                    RpcClient::new_sender(
+                        HttpSender::new_with_client(url, http_client.clone()),
+                        RpcClientConfig::with_commitment(commitment)
+                    )
+                ),
+                state: Mutex::new(EndpointState::default()),
+            })
+            .collect();
+
+        Self {
+            network: network.to_string(),
+            endpoints,
+            policy,
+            agreed_slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // HTTP client shared by the network's endpoints, sending its configured headers
+    fn http_client(network: &str, config: &NetworkConfig, timeout: Duration) -> reqwest::Client {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        for (name, value) in &config.headers {
+            match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+                (Ok(name), Ok(value)) => {
+                    headers.insert(name, value);
+                }
+                _ => warn!("Ignoring invalid RPC header {} of network {}", name, network),
+            }
+        }
+
+        reqwest::Client
+            ::builder()
+            .default_headers(headers)
+            .timeout(timeout)
+            .pool_idle_timeout(timeout)
+            .build()
+            .expect("build rpc client")
+    }
+
+    /// Runs `op` against the network's endpoints until it succeeds, a non-retryable error
+    /// occurs or the retry policy's attempts are exhausted.
+    ///
+    /// Every retry moves on to the next healthy endpoint in configured order.
+    pub async fn call<T, F, Fut>(&self, op: F) -> Result<T, SolResolverError>
+        where F: Fn(Arc<RpcClient>) -> Fut, Fut: Future<Output = Result<T, ClientError>>
+    {
+        let mut last_error = SolResolverError::RpcError(
+            format!("No RPC endpoints configured for {}", self.network)
+        );
+
+        for attempt in 0..self.policy.max_attempts.max(1) {
+            let endpoint = match self.select(attempt) {
+                Some(endpoint) => endpoint,
+                None => {
+                    break;
+                }
+            };
+
+            match op(endpoint.client.clone()).await {
+                Ok(value) => {
+                    self.record_success(endpoint);
+                    return Ok(value);
+                }
+                Err(e) if !Self::is_retryable(&e) => {
+                    return Err(SolResolverError::RpcError(e.to_string()));
+                }
+                Err(e) => {
+                    self.record_failure(endpoint);
+                    last_error = SolResolverError::RpcError(e.to_string());
+
+                    if attempt + 1 < self.policy.max_attempts {
+                        let delay = self.policy.backoff(attempt);
+                        warn!(
+                            "RPC call to {} failed (attempt {}/{}), retrying in {:?}: {}",
+                            endpoint.url,
+                            attempt + 1,
+                            self.policy.max_attempts,
+                            delay,
+                            e
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Runs `op` once against every endpoint concurrently, without retries, and returns
+    /// each endpoint's URL with its result in configured order.
+    pub async fn call_all<T, F, Fut>(&self, op: F) -> Vec<(String, Result<T, ClientError>)>
+        where F: Fn(Arc<RpcClient>) -> Fut, Fut: Future<Output = Result<T, ClientError>>
+    {
+        let calls = self.endpoints.iter().map(|endpoint| {
+            let call = op(endpoint.client.clone());
+            async move {
+                let result = call.await;
+                match &result {
+                    Ok(_) => self.record_success(endpoint),
+                    Err(e) if Self::is_retryable(e) => self.record_failure(endpoint),
+                    Err(_) => {}
+                }
+                (endpoint.url.clone(), result)
+            }
+        });
+
+        join_all(calls).await
+    }
+
+    pub fn network(&self) -> &str {
+        &self.network
+    }
+
+    pub fn agreed_slot(&self, commitment: CommitmentLevel) -> u64 {
+        self.agreed_slots.lock().unwrap().get(&commitment).copied().unwrap_or_default()
+    }
+
+    pub fn record_agreed_slot(&self, commitment: CommitmentLevel, slot: u64) {
+        let mut agreed_slots = self.agreed_slots.lock().unwrap();
+        let agreed_slot = agreed_slots.entry(commitment).or_default();
+        *agreed_slot = (*agreed_slot).max(slot);
+    }
+
+    /// Snapshot of every endpoint's health.
+    pub fn health(&self) -> Vec<EndpointHealth> {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .map(|endpoint| {
+                let state = endpoint.state.lock().unwrap();
+                EndpointHealth {
+                    network: self.network.clone(),
+                    url: endpoint.url.clone(),
+                    consecutive_failures: state.consecutive_failures,
+                    total_failures: state.total_failures,
+                    total_successes: state.total_successes,
+                    ejected: state.ejected_until.is_some_and(|until| until > now),
+                }
+            })
+            .collect()
+    }
+
+    // Picks the endpoint for an attempt, rotating through healthy endpoints in order. When
+    // every endpoint is ejected, the one closest to being restored is used.
+    fn select(&self, attempt: u32) -> Option<&Endpoint> {
+        let now = Instant::now();
+        let healthy: Vec<&Endpoint> = self.endpoints
+            .iter()
+            .filter(|endpoint| {
+                endpoint.state
+                    .lock()
+                    .unwrap()
+                    .ejected_until.is_none_or(|until| until <= now)
+            })
+            .collect();
+
+        if healthy.is_empty() {
+            return self.endpoints
+                .iter()
+                .min_by_key(|endpoint| endpoint.state.lock().unwrap().ejected_until);
+        }
+
+        Some(healthy[(attempt as usize) % healthy.len()])
+    }
+
+    fn record_success(&self, endpoint: &Endpoint) {
+        let mut state = endpoint.state.lock().unwrap();
+        if state.ejected_until.take().is_some() {
+            debug!("RPC endpoint {} restored", endpoint.url);
+        }
+        state.consecutive_failures = 0;
+        state.total_successes += 1;
+    }
+
+    fn record_failure(&self, endpoint: &Endpoint) {
+        let mut state = endpoint.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        state.total_failures += 1;
+
+        if state.consecutive_failures >= self.policy.eject_after {
+            warn!(
+                "Ejecting RPC endpoint {} for {:?} after {} consecutive failures",
+                endpoint.url,
+                self.policy.eject_duration,
+                state.consecutive_failures
+            );
+            state.ejected_until = Some(Instant::now() + self.policy.eject_duration);
+        }
+    }
+
+    // Transport failures, rate limiting, 5xx responses, timeouts and node-side server
+    // errors are worth retrying elsewhere; malformed or rejected requests are not
+    fn is_retryable(error: &ClientError) -> bool {
+        match error.kind() {
+            ClientErrorKind::Io(_) => true,
+            ClientErrorKind::Reqwest(error) => {
+                match error.status() {
+                    Some(status) => status.as_u16() == 429 || status.is_server_error(),
+                    None => error.is_timeout() || error.is_connect(),
+                }
+            }
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. }) => {
+                (-32099..=-32000).contains(code)
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::did_sol::DidSol;
+use crate::errors::*;
+use crate::key_agreement::{ derived_fragment, x25519_private_key, x25519_public_key };
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use async_trait::async_trait;
+use base64::{ engine::general_purpose::URL_SAFE_NO_PAD, Engine };
+use didcomm::secrets::{ Secret, SecretMaterial, SecretType, SecretsResolver };
+use log::debug;
+use serde_json::json;
+use solana_sdk::signature::{ read_keypair_file, Keypair, Signer };
+
+/// DIDComm secrets of did:sol identities, loaded from Solana CLI keypair files.
+///
+/// Every keypair registered under a fragment yields two secrets: the Ed25519 signing key
+/// `did:sol:<key>#<fragment>` and the derived X25519 key-agreement key
+/// `did:sol:<key>#<fragment>-x25519`, matching the keys `SolResolver` lists when
+/// `derive_key_agreement` is enabled.
+#[derive(Debug, Clone, Default)]
+pub struct SolKeypairSecretsResolver {
+    secrets: BTreeMap<String, Secret>,
+}
+
+impl SolKeypairSecretsResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the keypair file of a DID authority as the `#default` secrets of its DID on
+    /// `network`.
+    pub fn from_keypair_file(network: &str, path: &Path) -> Result<Self, SolResolverError> {
+        let keypair = load_keypair(path)?;
+        let did = DidSol::new(network, keypair.pubkey());
+
+        Ok(Self::new().with_keypair(&did, "default", &keypair))
+    }
+
+    /// Adds the secrets of `keypair` as the verification method `fragment` of `did`.
+    pub fn with_keypair(mut self, did: &DidSol, fragment: &str, keypair: &Keypair) -> Self {
+        let keypair_bytes = keypair.to_bytes();
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&keypair_bytes[..32]);
+        let public_key = keypair.pubkey().to_bytes();
+
+        let signing_secret = jwk_secret(
+            did.to_did_url(fragment),
+            "Ed25519",
+            &public_key,
+            &seed
+        );
+        debug!("Loaded secret {}", signing_secret.id);
+        self.secrets.insert(signing_secret.id.clone(), signing_secret);
+
+        // every valid Ed25519 public key maps to an X25519 key
+        if let Some(x25519_public) = x25519_public_key(&public_key) {
+            let key_agreement_secret = jwk_secret(
+                did.to_did_url(&derived_fragment(fragment)),
+                "X25519",
+                &x25519_public,
+                &x25519_private_key(&seed)
+            );
+            debug!("Loaded secret {}", key_agreement_secret.id);
+            self.secrets.insert(key_agreement_secret.id.clone(), key_agreement_secret);
+        }
+
+        self
+    }
+
+    /// Adds the secrets of a keypair file as the verification method `fragment` of `did`.
+    pub fn with_keypair_file(
+        self,
+        did: &DidSol,
+        fragment: &str,
+        path: &Path
+    ) -> Result<Self, SolResolverError> {
+        let keypair = load_keypair(path)?;
+        Ok(self.with_keypair(did, fragment, &keypair))
+    }
+
+    /// DID URLs of all loaded secrets.
+    pub fn secret_ids(&self) -> impl Iterator<Item = &str> {
+        self.secrets.keys().map(String::as_str)
+    }
+}
+
+#[async_trait(?Send)]
+impl SecretsResolver for SolKeypairSecretsResolver {
+    async fn get_secret(&self, secret_id: &str) -> didcomm::error::Result<Option<Secret>> {
+        Ok(self.secrets.get(secret_id).cloned())
+    }
+
+    async fn find_secrets<'a>(
+        &self,
+        secret_ids: &'a [&'a str]
+    ) -> didcomm::error::Result<Vec<&'a str>> {
+        Ok(
+            secret_ids
+                .iter()
+                .filter(|id| self.secrets.contains_key(**id))
+                .copied()
+                .collect()
+        )
+    }
+}
+
+// Reads a Solana CLI keypair file: a JSON array of the 64 secret and public key bytes
+fn load_keypair(path: &Path) -> Result<Keypair, SolResolverError> {
+    read_keypair_file(path).map_err(|e|
+        SolResolverError::InvalidKeypair(format!("{}: {}", path.display(), e))
+    )
+}
+
+// OKP private JWK (RFC 8037) wrapped as a JsonWebKey2020 secret
+fn jwk_secret(id: String, curve: &str, public_key: &[u8], private_key: &[u8]) -> Secret {
+    Secret {
+        id,
+        type_: SecretType::JsonWebKey2020,
+        secret_material: SecretMaterial::JWK {
+            private_key_jwk: json!({
+                "kty": "OKP",
+                "crv": curve,
+                "x": URL_SAFE_NO_PAD.encode(public_key),
+                "d": URL_SAFE_NO_PAD.encode(private_key),
+            }),
+        },
+    }
+}
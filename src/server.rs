@@ -0,0 +1,219 @@
+use crate::resolution::*;
+use crate::SolResolver;
+
+use std::sync::Arc;
+use axum::{
+    extract::{ Path, State },
+    http::{ header, HeaderMap, StatusCode },
+    response::{ IntoResponse, Response },
+    routing::get,
+    Json,
+    Router,
+};
+use log::info;
+use serde_json::json;
+
+/// Representation requested through the `Accept` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Representation {
+    DidDocument,
+    DidDocumentCbor,
+    ResolutionResult,
+}
+
+impl Representation {
+    // Media type of the document, also reported in the resolution metadata
+    fn document_content_type(&self) -> &'static str {
+        match self {
+            Representation::DidDocumentCbor => DID_CBOR,
+            Representation::DidDocument | Representation::ResolutionResult => DID_LD_JSON,
+        }
+    }
+
+    // Distinguishes the entity tags of the representations of one document
+    fn etag_suffix(&self) -> &'static str {
+        match self {
+            Representation::DidDocument => ".did",
+            Representation::DidDocumentCbor => ".cbor",
+            Representation::ResolutionResult => "",
+        }
+    }
+
+    // Picks the first supported media range; a missing header selects the resolution result
+    fn from_accept(headers: &HeaderMap) -> Option<Self> {
+        let accept = match headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()) {
+            Some(accept) => accept,
+            None => {
+                return Some(Representation::ResolutionResult);
+            }
+        };
+
+        accept.split(',').find_map(|range| {
+            let range = range.trim();
+            let media_type = range.split(';').next().unwrap_or("").trim();
+            match media_type {
+                "application/did+ld+json" | "application/did+json" => {
+                    Some(Representation::DidDocument)
+                }
+                "application/did+cbor" => Some(Representation::DidDocumentCbor),
+                "application/ld+json" if range.contains("https://w3id.org/did-resolution") => {
+                    Some(Representation::ResolutionResult)
+                }
+                "application/json" | "application/*" | "*/*" => {
+                    Some(Representation::ResolutionResult)
+                }
+                _ => None,
+            }
+        })
+    }
+}
+
+/// Builds the Universal Resolver driver routes around `resolver`.
+pub fn router(resolver: SolResolver) -> Router {
+    Router::new()
+        .route("/1.0/identifiers/:did", get(resolve_identifier))
+        .route("/1.0/properties", get(properties))
+        .route("/health", get(health))
+        .with_state(Arc::new(resolver))
+}
+
+fn status_code(result: &ResolutionResult) -> StatusCode {
+    match result.did_resolution_metadata.error {
+        Some(ResolutionError::InvalidDid) => StatusCode::BAD_REQUEST,
+        Some(ResolutionError::NotFound) => StatusCode::NOT_FOUND,
+        Some(ResolutionError::RepresentationNotSupported) => StatusCode::NOT_ACCEPTABLE,
+        Some(ResolutionError::MethodNotSupported) => StatusCode::NOT_IMPLEMENTED,
+        Some(ResolutionError::InternalError) => StatusCode::INTERNAL_SERVER_ERROR,
+        None if result.is_deactivated() => StatusCode::GONE,
+        None => StatusCode::OK,
+    }
+}
+
+async fn resolve_identifier(
+    State(resolver): State<Arc<SolResolver>>,
+    Path(did): Path<String>,
+    headers: HeaderMap
+) -> Response {
+    info!("Driver request for DID: {}", did);
+
+    let representation = match Representation::from_accept(&headers) {
+        Some(representation) => representation,
+        None => {
+            let result = ResolutionResult::error(
+                ResolutionError::RepresentationNotSupported,
+                "Requested representation is not supported"
+            );
+            return resolution_response(result);
+        }
+    };
+
+    let snapshot = match resolver.fetch_account(&did).await {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            return resolution_response(
+                ResolutionResult::from_resolution(Err(e), representation.document_content_type())
+            );
+        }
+    };
+
+    let result = ResolutionResult::from_resolution(
+        resolver.build_document(&snapshot),
+        representation.document_content_type()
+    );
+    let status = status_code(&result);
+
+    // Responses are keyed by the account data and the slot it was read at, and vary with
+    // the requested representation
+    let etag = format!("\"{}.{}{}\"", snapshot.data_hash(), snapshot.slot, representation.etag_suffix());
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (
+            header::CACHE_CONTROL,
+            format!("max-age={}", resolver.config().cache_max_age.as_secs()),
+        ),
+        (header::VARY, "Accept".to_string()),
+    ];
+    // only a document that would be served can be not modified
+    if status == StatusCode::OK && if_none_match(&headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    match (representation, status) {
+        (Representation::DidDocument, StatusCode::OK) => {
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, DID_LD_JSON)],
+                cache_headers,
+                Json(result.did_document),
+            ).into_response()
+        }
+        (Representation::DidDocumentCbor, StatusCode::OK) => {
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, DID_CBOR)],
+                cache_headers,
+                result.document_stream().unwrap_or_default(),
+            ).into_response()
+        }
+        (Representation::ResolutionResult, StatusCode::OK) => {
+            (cache_headers, resolution_response(result)).into_response()
+        }
+        _ => resolution_response(result),
+    }
+}
+
+// Whether `If-None-Match` lists `etag` (compared weakly) or is `*`
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim().trim_start_matches("W/"))
+        .any(|tag| tag == "*" || tag == etag)
+}
+
+fn resolution_response(result: ResolutionResult) -> Response {
+    (
+        status_code(&result),
+        [
+            (header::CONTENT_TYPE, DID_RESOLUTION_LD_JSON),
+            (header::VARY, "Accept"),
+        ],
+        Json(result),
+    ).into_response()
+}
+
+async fn properties(State(resolver): State<Arc<SolResolver>>) -> Json<serde_json::Value> {
+    let config = resolver.config();
+    let networks: serde_json::Map<String, serde_json::Value> = config.networks
+        .iter()
+        .map(|(name, network)| {
+            let program_id = config.network_program_id(name).to_string();
+            let rpc_urls: Vec<String> = network.rpc_urls
+                .iter()
+                .filter_map(|url| redacted_origin(url))
+                .collect();
+            (name.clone(), json!({ "rpcUrls": rpc_urls, "programId": program_id }))
+        })
+        .collect();
+
+    Json(
+        json!({
+            "programId": config.program_id().to_string(),
+            "strict": config.strict,
+            "networks": networks,
+        })
+    )
+}
+
+// Hosted RPC providers put API keys in the credentials, path or query of their URLs, so
+// only the scheme, host and port are published
+fn redacted_origin(url: &str) -> Option<String> {
+    let origin = reqwest::Url::parse(url).ok()?.origin();
+    origin.is_tuple().then(|| origin.ascii_serialization())
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(json!({ "status": "ok" }))
+}
@@ -0,0 +1,160 @@
+use crate::account_source::AccountSource;
+use crate::config::SolResolverConfig;
+use crate::did_sol::DidSol;
+use crate::errors::*;
+use crate::registrar::DidRegistrar;
+use crate::resolver::SolResolver;
+
+use async_trait::async_trait;
+use log::debug;
+use solana_program_test::{ processor, BanksClient, ProgramTest, ProgramTestContext };
+use solana_sdk::account::{ from_account, Account };
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::clock::Clock;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{ Keypair, Signer };
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+
+/// Network name of the DIDs created on the test bank.
+pub const TEST_NETWORK: &str = "localnet";
+/// DID account size `create_did` allocates, room for a handful of keys and services.
+pub const TEST_DID_ACCOUNT_SIZE: u32 = 10_000;
+
+/// A `solana-program-test` bank running the sol-did program, for offline tests.
+///
+/// The program is loaded at the program ID configured for `localnet`. It runs natively
+/// from the `sol_did` crate, whose entrypoint only accepts the program ID it declares;
+/// set `SBF_OUT_DIR` (or `BPF_OUT_DIR`) to a directory holding `sol_did.so` to run a
+/// compiled program deployed under another ID instead.
+pub struct DidTestBank {
+    context: ProgramTestContext,
+    config: SolResolverConfig,
+}
+
+impl DidTestBank {
+    /// Boots a bank with the sol-did program at the default program ID.
+    pub async fn start() -> Self {
+        Self::start_with(SolResolverConfig::default()).await
+    }
+
+    /// Boots a bank with the sol-did program at the program ID `config` uses for
+    /// `localnet`; resolvers handed out by the bank use the same configuration.
+    pub async fn start_with(config: SolResolverConfig) -> Self {
+        let program_id = config.network_program_id(TEST_NETWORK);
+        debug!("Starting test bank with sol-did program {}", program_id);
+
+        let program_test = ProgramTest::new("sol_did", program_id, processor!(process_instruction));
+        let context = program_test.start_with_context().await;
+
+        Self { context, config }
+    }
+
+    /// Keypair funded by the bank, paying for every transaction it processes.
+    pub fn payer(&self) -> &Keypair {
+        &self.context.payer
+    }
+
+    pub fn banks_client(&self) -> BanksClient {
+        self.context.banks_client.clone()
+    }
+
+    /// DID of `authority` on the test network.
+    pub fn did(&self, authority: &Pubkey) -> DidSol {
+        DidSol::new(TEST_NETWORK, *authority)
+    }
+
+    /// Registrar building instructions for the DID of `authority`.
+    pub fn registrar(&self, authority: &Pubkey) -> DidRegistrar {
+        DidRegistrar::from_config(self.did(authority), &self.config)
+    }
+
+    /// Resolver reading DID accounts from the bank.
+    pub fn resolver(&self) -> SolResolver {
+        SolResolver::new(self.config.clone()).with_account_source(BanksAccountSource {
+            banks_client: self.banks_client(),
+        })
+    }
+
+    /// Creates the DID account of `authority` with `TEST_DID_ACCOUNT_SIZE` bytes.
+    pub async fn create_did(&mut self, authority: &Keypair) -> Result<DidSol, SolResolverError> {
+        let registrar = self.registrar(&authority.pubkey());
+        let instruction = registrar.initialize(TEST_DID_ACCOUNT_SIZE, &self.payer().pubkey());
+        self.process(&[instruction], &[authority]).await?;
+
+        Ok(registrar.did().clone())
+    }
+
+    /// Processes instructions in one transaction signed by the payer and `signers`.
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair]
+    ) -> Result<(), SolResolverError> {
+        let blockhash = self.context.banks_client
+            .get_latest_blockhash().await
+            .map_err(|e| SolResolverError::RpcError(e.to_string()))?;
+
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let mut transaction = Transaction::new_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey())
+        );
+        transaction
+            .try_sign(&all_signers, blockhash)
+            .map_err(|e| SolResolverError::SigningError(e.to_string()))?;
+
+        self.context.banks_client
+            .process_transaction(transaction).await
+            .map_err(|e| SolResolverError::RpcError(e.to_string()))
+    }
+}
+
+/// Reads accounts from a `solana-program-test` bank.
+#[derive(Clone)]
+pub struct BanksAccountSource {
+    pub banks_client: BanksClient,
+}
+
+#[async_trait]
+impl AccountSource for BanksAccountSource {
+    async fn get_account(
+        &self,
+        address: &Pubkey,
+        commitment: CommitmentConfig
+    ) -> Result<(Option<Account>, u64), SolResolverError> {
+        let mut banks_client = self.banks_client.clone();
+        let account = banks_client
+            .get_account_with_commitment(*address, commitment.commitment).await
+            .map_err(|e| SolResolverError::RpcError(e.to_string()))?;
+
+        // the clock read at the same commitment gives the slot of the bank the account
+        // came from, which the root slot only matches at `finalized`
+        let clock = banks_client
+            .get_account_with_commitment(sysvar::clock::ID, commitment.commitment).await
+            .map_err(|e| SolResolverError::RpcError(e.to_string()))?
+            .and_then(|clock| from_account::<Clock, _>(&clock))
+            .ok_or_else(|| SolResolverError::RpcError("Clock sysvar unavailable".to_string()))?;
+
+        Ok((account, clock.slot))
+    }
+}
+
+// The Anchor entrypoint ties the accounts slice to the account infos' lifetime, which the
+// native processor signature cannot express
+fn process_instruction<'a, 'info>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'info>],
+    data: &[u8]
+) -> ProgramResult {
+    // SAFETY: only the slice's lifetime is widened, and the entrypoint does not keep
+    // references to it once it returns, so it is never used beyond `'a`
+    let accounts = unsafe {
+        std::mem::transmute::<&'a [AccountInfo<'info>], &'info [AccountInfo<'info>]>(accounts)
+    };
+    sol_did::entry(program_id, accounts, data)
+}
@@ -0,0 +1,347 @@
+use crate::did_sol::DidSol;
+use crate::errors::*;
+use crate::resolver::SolResolver;
+use crate::w3c_doc::{ decode_hex, PublicKeyFormat, VerificationMethod, W3cDidDocument };
+
+use std::fmt;
+use base64::{ engine::general_purpose::URL_SAFE_NO_PAD, Engine };
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{ Digest, Sha256 };
+use solana_sdk::keccak;
+
+// Multicodec prefix of an Ed25519 public key in a multibase value
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+// Multicodec prefix of a compressed secp256k1 public key in a multibase value
+const SECP256K1_MULTICODEC: [u8; 2] = [0xe7, 0x01];
+
+/// Verification relationship a key must be listed under to be accepted for a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationRelationship {
+    Authentication,
+    AssertionMethod,
+    KeyAgreement,
+    CapabilityInvocation,
+    CapabilityDelegation,
+}
+
+impl VerificationRelationship {
+    // Ids of the methods listed under the relationship
+    fn ids<'a>(&self, doc: &'a W3cDidDocument) -> &'a [String] {
+        match self {
+            VerificationRelationship::Authentication => &doc.authentication,
+            VerificationRelationship::AssertionMethod => &doc.assertion_method,
+            VerificationRelationship::KeyAgreement => &doc.key_agreement,
+            VerificationRelationship::CapabilityInvocation => &doc.capability_invocation,
+            VerificationRelationship::CapabilityDelegation => &doc.capability_delegation,
+        }
+    }
+}
+
+impl fmt::Display for VerificationRelationship {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            VerificationRelationship::Authentication => "authentication",
+            VerificationRelationship::AssertionMethod => "assertionMethod",
+            VerificationRelationship::KeyAgreement => "keyAgreement",
+            VerificationRelationship::CapabilityInvocation => "capabilityInvocation",
+            VerificationRelationship::CapabilityDelegation => "capabilityDelegation",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Public key of a verification method, decoded from its verification material
+enum PublicKey {
+    Ed25519(ed25519_dalek::PublicKey),
+    Secp256k1(libsecp256k1::PublicKey),
+    // EcdsaSecp256k1RecoveryMethod2020 keys are only known by their address
+    EthereumAddress([u8; 20]),
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JwsHeader {
+    pub alg: String,
+    pub kid: Option<String>,
+    #[serde(default = "default_b64")]
+    pub b64: bool,
+    /// Header parameters the verifier must understand (RFC 7515 §4.1.11)
+    #[serde(default)]
+    pub crit: Vec<String>,
+}
+
+fn default_b64() -> bool {
+    true
+}
+
+// Compact JWS split into its segments; the payload segment is empty when detached
+pub(crate) struct CompactJws<'a> {
+    pub header: JwsHeader,
+    pub encoded_header: &'a str,
+    pub encoded_payload: &'a str,
+    pub signature: Vec<u8>,
+}
+
+impl<'a> CompactJws<'a> {
+    pub(crate) fn parse(jws: &'a str) -> Result<Self, SolResolverError> {
+        let (encoded_header, encoded_payload, encoded_signature) = match jws.split('.').collect::<Vec<_>>()[..] {
+            [header, payload, signature] => (header, payload, signature),
+            _ => {
+                return Err(SolResolverError::InvalidSignature("JWS is not in compact form".to_string()));
+            }
+        };
+
+        let header: JwsHeader = URL_SAFE_NO_PAD.decode(encoded_header)
+            .ok()
+            .and_then(|header| serde_json::from_slice(&header).ok())
+            .ok_or_else(|| SolResolverError::InvalidSignature("Invalid JWS header".to_string()))?;
+        // `b64` is the only extension understood, and RFC 7797 requires it to be critical
+        if let Some(unknown) = header.crit.iter().find(|name| *name != "b64") {
+            return Err(SolResolverError::InvalidSignature(format!("Unsupported critical JWS header {}", unknown)));
+        }
+        if !header.b64 && !header.crit.iter().any(|name| name == "b64") {
+            return Err(SolResolverError::InvalidSignature("Unencoded JWS payload without crit b64".to_string()));
+        }
+        let signature = URL_SAFE_NO_PAD.decode(encoded_signature).map_err(|_|
+            SolResolverError::InvalidSignature("Invalid JWS signature encoding".to_string())
+        )?;
+
+        Ok(Self { header, encoded_header, encoded_payload, signature })
+    }
+
+    // Signing input of a detached JWS over `payload`, encoded unless `"b64": false`
+    pub(crate) fn detached_signing_input(&self, payload: &[u8]) -> Result<Vec<u8>, SolResolverError> {
+        if !self.encoded_payload.is_empty() {
+            return Err(SolResolverError::InvalidSignature("JWS payload is not detached".to_string()));
+        }
+
+        let mut signing_input = format!("{}.", self.encoded_header).into_bytes();
+        if self.header.b64 {
+            signing_input.extend(URL_SAFE_NO_PAD.encode(payload).into_bytes());
+        } else {
+            signing_input.extend_from_slice(payload);
+        }
+        Ok(signing_input)
+    }
+}
+
+impl SolResolver {
+    /// Verifies `signature` over `message` with the did:sol verification method `kid`
+    /// (a DID URL such as `did:sol:devnet:<key>#default`), returning the method.
+    ///
+    /// The DID is resolved with this resolver and the method must be listed under
+    /// `relationship`. Signatures are checked against the method's material:
+    ///
+    /// - Ed25519 keys: a 64 byte Ed25519 signature of `message`
+    /// - secp256k1 keys: a 64 byte `r || s` ECDSA signature of the SHA-256 of `message`,
+    ///   as in ES256K
+    /// - Ethereum addresses (`EcdsaSecp256k1RecoveryMethod2020`): a 65 byte `r || s || v`
+    ///   `personal_sign` signature of `message`, the way sol-did checks Ethereum signers
+    pub async fn verify(
+        &self,
+        kid: &str,
+        message: &[u8],
+        signature: &[u8],
+        relationship: VerificationRelationship
+    ) -> Result<VerificationMethod, SolResolverError> {
+        let vm = self.authorized_method(kid, relationship).await?;
+        verify_signature(&public_key(&vm)?, message, signature)?;
+
+        Ok(vm)
+    }
+
+    /// Verifies a detached compact JWS (`<header>..<signature>`) over `payload`, returning
+    /// the verification method named by the header's `kid`.
+    ///
+    /// `EdDSA` requires an Ed25519 key and `ES256K` a secp256k1 key; unencoded payloads
+    /// (`"b64": false`, RFC 7797) are supported.
+    pub async fn verify_jws(
+        &self,
+        jws: &str,
+        payload: &[u8],
+        relationship: VerificationRelationship
+    ) -> Result<VerificationMethod, SolResolverError> {
+        let jws = CompactJws::parse(jws)?;
+        let signing_input = jws.detached_signing_input(payload)?;
+        let kid = jws.header.kid
+            .as_deref()
+            .ok_or_else(|| SolResolverError::InvalidSignature("JWS header has no kid".to_string()))?;
+
+        self.verify_with_algorithm(kid, &jws.header.alg, &signing_input, &jws.signature, relationship).await
+    }
+
+    // Like `verify`, additionally requiring the key type of the JWS algorithm `alg`
+    pub(crate) async fn verify_with_algorithm(
+        &self,
+        kid: &str,
+        alg: &str,
+        signing_input: &[u8],
+        signature: &[u8],
+        relationship: VerificationRelationship
+    ) -> Result<VerificationMethod, SolResolverError> {
+        let vm = self.authorized_method(kid, relationship).await?;
+        let key = public_key(&vm)?;
+        match (alg, &key) {
+            ("EdDSA", PublicKey::Ed25519(_)) | ("ES256K", PublicKey::Secp256k1(_)) => {}
+            (alg, _) => {
+                return Err(
+                    SolResolverError::InvalidSignature(
+                        format!("Algorithm {} does not match {} ({})", alg, vm.id, vm.type_)
+                    )
+                );
+            }
+        }
+        verify_signature(&key, signing_input, signature)?;
+
+        Ok(vm)
+    }
+
+    // Resolves the DID of `kid` and returns the method if it is listed under `relationship`
+    async fn authorized_method(
+        &self,
+        kid: &str,
+        relationship: VerificationRelationship
+    ) -> Result<VerificationMethod, SolResolverError> {
+        let (did, fragment) = kid
+            .split_once('#')
+            .ok_or_else(|| SolResolverError::DidUrlNotFound(kid.to_string()))?;
+        // the document lists methods under the normalized DID
+        let did_sol: DidSol = did.parse()?;
+        let kid = did_sol.to_did_url(fragment);
+
+        let doc = self.resolve_w3c(&did_sol.to_string()).await?;
+        if !relationship.ids(&doc).contains(&kid) {
+            return Err(SolResolverError::KeyNotAuthorized {
+                kid,
+                relationship: relationship.to_string(),
+            });
+        }
+
+        doc.verification_method
+            .into_iter()
+            .find(|vm| vm.id == kid)
+            .ok_or(SolResolverError::DidUrlNotFound(kid))
+    }
+}
+
+fn verify_signature(key: &PublicKey, message: &[u8], signature: &[u8]) -> Result<(), SolResolverError> {
+    let valid = match key {
+        PublicKey::Ed25519(key) =>
+            ed25519_dalek::Signature
+                ::try_from(signature)
+                .map(|signature| key.verify_strict(message, &signature).is_ok())
+                .unwrap_or(false),
+        PublicKey::Secp256k1(key) => {
+            let digest: [u8; 32] = Sha256::digest(message).into();
+            libsecp256k1::Signature
+                ::parse_standard_slice(signature)
+                .map(|signature| {
+                    libsecp256k1::verify(&libsecp256k1::Message::parse(&digest), &signature, key)
+                })
+                .unwrap_or(false)
+        }
+        PublicKey::EthereumAddress(address) => recover_address(message, signature) == Some(*address),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(SolResolverError::InvalidSignature("Signature does not verify".to_string()))
+    }
+}
+
+// Address of the key that produced a `personal_sign` signature of `message`
+fn recover_address(message: &[u8], signature: &[u8]) -> Option<[u8; 20]> {
+    if signature.len() != 65 {
+        return None;
+    }
+    let recovery_id = match signature[64] {
+        v @ 27..=28 => v - 27,
+        v => v,
+    };
+
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", message.len());
+    let digest = keccak::hashv(&[prefix.as_bytes(), message]).to_bytes();
+    let key = libsecp256k1::recover(
+        &libsecp256k1::Message::parse(&digest),
+        &libsecp256k1::Signature::parse_standard_slice(&signature[..64]).ok()?,
+        &libsecp256k1::RecoveryId::parse(recovery_id).ok()?
+    ).ok()?;
+
+    let hash = keccak::hash(&key.serialize()[1..]).to_bytes();
+    hash[12..].try_into().ok()
+}
+
+// Decodes the public key of a verification method from its type and material
+fn public_key(vm: &VerificationMethod) -> Result<PublicKey, SolResolverError> {
+    let unsupported = || SolResolverError::UnsupportedVerificationMethod(format!("{} ({})", vm.id, vm.type_));
+    let invalid = || SolResolverError::InvalidSignature(format!("Invalid public key of {}", vm.id));
+
+    match (vm.type_.as_str(), &vm.public_key) {
+        ("EcdsaSecp256k1RecoveryMethod2020", PublicKeyFormat::EthereumAddress { ethereum_address }) => {
+            let address = decode_hex(ethereum_address).ok_or_else(invalid)?;
+            Ok(PublicKey::EthereumAddress(address.try_into().map_err(|_| invalid())?))
+        }
+        (_, PublicKeyFormat::Jwk { public_key_jwk }) => jwk_public_key(public_key_jwk).ok_or_else(invalid),
+        ("Ed25519VerificationKey2018" | "Ed25519VerificationKey2020", material) => {
+            let key = match material {
+                PublicKeyFormat::Base58 { public_key_base58 } =>
+                    bs58::decode(public_key_base58).into_vec().ok(),
+                PublicKeyFormat::Multibase { public_key_multibase } =>
+                    decode_multikey(public_key_multibase, &ED25519_MULTICODEC, &[32]),
+                _ => None,
+            }.ok_or_else(invalid)?;
+            ed25519_public_key(&key).ok_or_else(invalid)
+        }
+        ("EcdsaSecp256k1VerificationKey2019", material) => {
+            let key = match material {
+                PublicKeyFormat::Hex { public_key_hex } => decode_hex(public_key_hex),
+                PublicKeyFormat::Base58 { public_key_base58 } =>
+                    bs58::decode(public_key_base58).into_vec().ok(),
+                PublicKeyFormat::Multibase { public_key_multibase } =>
+                    decode_multikey(public_key_multibase, &SECP256K1_MULTICODEC, &[33, 65]),
+                _ => None,
+            }.ok_or_else(invalid)?;
+            secp256k1_public_key(&key).ok_or_else(invalid)
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+// Ed25519 (OKP) and secp256k1 (EC) JSON Web Keys
+fn jwk_public_key(jwk: &Value) -> Option<PublicKey> {
+    let coordinate = |name: &str| URL_SAFE_NO_PAD.decode(jwk.get(name)?.as_str()?).ok();
+
+    match (jwk.get("kty")?.as_str()?, jwk.get("crv")?.as_str()?) {
+        ("OKP", "Ed25519") => ed25519_public_key(&coordinate("x")?),
+        ("EC", "secp256k1") => {
+            let mut key = vec![0x04];
+            key.extend(coordinate("x")?);
+            key.extend(coordinate("y")?);
+            secp256k1_public_key(&key)
+        }
+        _ => None,
+    }
+}
+
+fn ed25519_public_key(key: &[u8]) -> Option<PublicKey> {
+    ed25519_dalek::PublicKey::from_bytes(key).ok().map(PublicKey::Ed25519)
+}
+
+// Compressed (33 byte) or uncompressed (65 byte) secp256k1 keys
+fn secp256k1_public_key(key: &[u8]) -> Option<PublicKey> {
+    libsecp256k1::PublicKey::parse_slice(key, None).ok().map(PublicKey::Secp256k1)
+}
+
+// Base58btc multibase value
+pub(crate) fn decode_multibase(multibase: &str) -> Option<Vec<u8>> {
+    bs58::decode(multibase.strip_prefix('z')?).into_vec().ok()
+}
+
+// Multikey: a base58btc multibase key behind its type's multicodec prefix, of one of the
+// key type's `lengths`
+fn decode_multikey(multibase: &str, multicodec: &[u8], lengths: &[usize]) -> Option<Vec<u8>> {
+    let bytes = decode_multibase(multibase)?;
+    let key = bytes.strip_prefix(multicodec)?;
+    lengths.contains(&key.len()).then(|| key.to_vec())
+}
@@ -1,34 +1,88 @@
-use didcomm::did::DIDResolver;
-use didcomm::did::ServiceKind;
-use serde::{ Serialize, Deserialize };
+use didcomm::did::{ DIDDoc, ServiceKind, VerificationMaterial, VerificationMethodType };
+use serde::{ Serialize, Deserialize, Deserializer };
+use serde_json::{ json, Value };
+use sol_did::state::DidAccount;
+use crate::canonical::{ canonicalize, DocumentDigest };
+use crate::constants::*;
+use crate::did_sol::DidSol;
+use crate::errors::SolResolverError;
 use crate::SolResolver;
 
-#[derive(Debug, Serialize, Deserialize)]
+// JSON-LD contexts of did:sol documents, as published by the sol-did client
+const SOL_DID_CONTEXTS: [&str; 2] = ["https://w3id.org/did/v1.0", "https://w3id.org/sol/v3.0"];
+
+// sol-did verification method types
+const METHOD_TYPE_ED25519_VERIFICATION_KEY_2018: u8 = 0;
+const METHOD_TYPE_ECDSA_SECP256K1_RECOVERY_METHOD_2020: u8 = 1;
+const METHOD_TYPE_ECDSA_SECP256K1_VERIFICATION_KEY_2019: u8 = 2;
+
+const DIDCOMM_MESSAGING_SERVICE_TYPE: &str = "DIDCommMessaging";
+
+// Flags of the default key of DIDs without an account, as set by `initialize`
+const GENERATIVE_DEFAULT_FLAGS: u16 = VM_FLAG_CAPABILITY_INVOCATION | VM_FLAG_OWNERSHIP_PROOF;
+
+/// A W3C DID document.
+///
+/// Deserializes any W3C DID document whose `@context` entries are strings: `@context` and
+/// `controller` may be a single string, and verification methods embedded in a
+/// verification relationship are moved to `verificationMethod`, leaving their id behind.
+/// Like the sol-did client, it serializes the controller, verification method, relationship
+/// and service arrays even when they are empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "W3cDidDocumentJson")]
 pub struct W3cDidDocument {
     #[serde(rename = "@context")]
     pub context: Vec<String>,
     pub id: String,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub also_known_as: Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub controller: Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub verification_method: Vec<VerificationMethod>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub authentication: Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub assertion_method: Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub key_agreement: Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub capability_invocation: Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub capability_delegation: Vec<String>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub service: Vec<Service>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// Published form of a W3C DID document, before embedded verification methods are moved
+// out of the verification relationships
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct W3cDidDocumentJson {
+    #[serde(rename = "@context", default, deserialize_with = "one_or_many")]
+    context: Vec<String>,
+    id: String,
+    #[serde(default)]
+    also_known_as: Vec<String>,
+    #[serde(default, deserialize_with = "one_or_many")]
+    controller: Vec<String>,
+    #[serde(default)]
+    verification_method: Vec<VerificationMethod>,
+    #[serde(default)]
+    authentication: Vec<RelationshipEntry>,
+    #[serde(default)]
+    assertion_method: Vec<RelationshipEntry>,
+    #[serde(default)]
+    key_agreement: Vec<RelationshipEntry>,
+    #[serde(default)]
+    capability_invocation: Vec<RelationshipEntry>,
+    #[serde(default)]
+    capability_delegation: Vec<RelationshipEntry>,
+    #[serde(default)]
+    service: Vec<Service>,
+}
+
+// A verification relationship lists methods by reference or embeds them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RelationshipEntry {
+    Reference(String),
+    Embedded(VerificationMethod),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum PublicKeyFormat {
     Base58 {
@@ -39,9 +93,23 @@ pub enum PublicKeyFormat {
         #[serde(rename = "publicKeyMultibase")]
         public_key_multibase: String,
     },
+    Jwk {
+        #[serde(rename = "publicKeyJwk")]
+        public_key_jwk: serde_json::Value,
+    },
+    Hex {
+        #[serde(rename = "publicKeyHex")]
+        public_key_hex: String,
+    },
+    EthereumAddress {
+        #[serde(rename = "ethereumAddress")]
+        ethereum_address: String,
+    },
+    /// Any other material, e.g. `blockchainAccountId` or `publicKeyPem`, kept as published
+    Other(serde_json::Value),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VerificationMethod {
     pub id: String,
     #[serde(rename = "type")]
@@ -51,80 +119,171 @@ pub struct VerificationMethod {
     pub public_key: PublicKeyFormat,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A service; the endpoint is a URI string for did:sol services, but may be any JSON,
+/// e.g. the `{"uri", "accept", "routingKeys"}` map of a DIDComm v2 service.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Service {
     pub id: String,
     #[serde(rename = "type")]
     pub type_: String,
-    pub service_endpoint: String,
+    pub service_endpoint: Value,
 }
 
 impl W3cDidDocument {
+    /// Resolves a did:sol identifier with a default `SolResolver`; use `resolve_with` to
+    /// apply a resolver's RPC, cache and program ID configuration.
     pub async fn resolve(did: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let resolver = SolResolver::default();
-        let did_doc = resolver.resolve(did).await?.ok_or("DID document not found")?;
+        Ok(Self::resolve_with(&SolResolver::default(), did).await?)
+    }
 
-        // Convert to W3C format
+    /// Resolves a did:sol identifier with `resolver`, see `SolResolver::resolve_w3c`.
+    pub async fn resolve_with(resolver: &SolResolver, did: &str) -> Result<Self, SolResolverError> {
+        resolver.resolve_w3c(did).await
+    }
+
+    /// Builds the document of a did:sol identifier from its DID account, `None` for
+    /// chainless DIDs, the way the sol-did client's `resolve()` does.
+    ///
+    /// The default key comes first, followed by the account's verification methods; each
+    /// method is listed under the relationships its flags select, and methods flagged
+    /// `didDocHidden` are left out entirely. Native controllers become did:sol identifiers
+    /// on the DID's network.
+    pub fn from_did_account(did: &DidSol, did_account: Option<&DidAccount>) -> Self {
+        let id = did.to_string();
         let mut doc = Self {
-            context: Self::default_context(),
-            id: did.to_string(),
+            context: SOL_DID_CONTEXTS.iter().map(|context| context.to_string()).collect(),
+            id: id.clone(),
             also_known_as: vec![],
             controller: vec![],
             verification_method: vec![],
-            authentication: did_doc.authentication.clone(),
+            authentication: vec![],
             assertion_method: vec![],
-            key_agreement: did_doc.key_agreement,
-            capability_invocation: did_doc.authentication.clone(),
+            key_agreement: vec![],
+            capability_invocation: vec![],
             capability_delegation: vec![],
             service: vec![],
         };
 
-        // Convert verification methods
-        doc.verification_method = did_doc.verification_method
-            .into_iter()
-            .map(|vm| VerificationMethod {
-                id: vm.id,
-                type_: Self::verification_type_to_string(&vm.type_),
-                controller: vm.controller,
-                public_key: match vm.verification_material {
-                    didcomm::did::VerificationMaterial::Base58 { public_key_base58 } =>
-                        PublicKeyFormat::Base58 { public_key_base58 },
-                    didcomm::did::VerificationMaterial::Multibase { public_key_multibase } =>
-                        PublicKeyFormat::Multibase { public_key_multibase },
-                    _ => PublicKeyFormat::Base58 { public_key_base58: String::new() },
-                },
-            })
-            .collect();
+        let generative_default = sol_did::state::VerificationMethod {
+            fragment: "default".to_string(),
+            flags: GENERATIVE_DEFAULT_FLAGS,
+            method_type: METHOD_TYPE_ED25519_VERIFICATION_KEY_2018,
+            key_data: did.pubkey().to_bytes().to_vec(),
+        };
+        let (default_method, verification_methods) = match did_account {
+            Some(did_account) =>
+                (&did_account.initial_verification_method, did_account.verification_methods.as_slice()),
+            None => (&generative_default, &[][..]),
+        };
 
-        doc.service = did_doc.service
-            .into_iter()
-            .map(|svc| {
-                match svc.service_endpoint {
-                    ServiceKind::Other { value } =>
-                        Service {
-                            id: svc.id,
-                            type_: value
-                                .get("type")
-                                .and_then(|t| t.as_str())
-                                .unwrap_or("UnknownType")
-                                .to_string(),
-                            service_endpoint: value
-                                .get("serviceEndpoint")
-                                .and_then(|e| e.as_str())
-                                .unwrap_or("")
-                                .to_string(),
-                        },
-                    _ =>
-                        Service {
-                            id: svc.id,
-                            type_: "UnknownType".to_string(),
-                            service_endpoint: "".to_string(),
-                        },
+        for vm in std::iter::once(default_method).chain(verification_methods) {
+            if vm.flags & VM_FLAG_DID_DOC_HIDDEN != 0 {
+                continue;
+            }
+            let vm_id = did.to_did_url(&vm.fragment);
+
+            for (flag, relationship) in [
+                (VM_FLAG_AUTHENTICATION, &mut doc.authentication),
+                (VM_FLAG_ASSERTION, &mut doc.assertion_method),
+                (VM_FLAG_KEY_AGREEMENT, &mut doc.key_agreement),
+                (VM_FLAG_CAPABILITY_INVOCATION, &mut doc.capability_invocation),
+                (VM_FLAG_CAPABILITY_DELEGATION, &mut doc.capability_delegation),
+            ] {
+                if vm.flags & flag != 0 {
+                    relationship.push(vm_id.clone());
                 }
-            })
-            .collect();
+            }
+
+            doc.verification_method.push(VerificationMethod {
+                id: vm_id,
+                type_: Self::method_type_to_string(vm.method_type),
+                controller: id.clone(),
+                public_key: Self::method_public_key(vm.method_type, &vm.key_data),
+            });
+        }
+
+        if let Some(did_account) = did_account {
+            doc.controller = did_account.native_controllers
+                .iter()
+                .map(|controller| DidSol::new(did.network(), *controller).to_string())
+                .chain(did_account.other_controllers.iter().cloned())
+                .collect();
+
+            doc.service = did_account.services
+                .iter()
+                .map(|service| Service {
+                    id: did.to_did_url(&service.fragment),
+                    type_: service.service_type.clone(),
+                    service_endpoint: Value::String(service.service_endpoint.clone()),
+                })
+                .collect();
+        }
+
+        doc
+    }
+
+    /// Serializes the document per RFC 8785 (JCS) after putting the parts that are sets in
+    /// a fixed order: `alsoKnownAs`, controllers and verification relationships are sorted
+    /// and deduplicated, verification methods and services sorted by id. `@context` keeps
+    /// its order, which is significant in JSON-LD.
+    pub fn canonical_json(&self) -> String {
+        let mut doc = self.clone();
+        for ids in [
+            &mut doc.also_known_as,
+            &mut doc.controller,
+            &mut doc.authentication,
+            &mut doc.assertion_method,
+            &mut doc.key_agreement,
+            &mut doc.capability_invocation,
+            &mut doc.capability_delegation,
+        ] {
+            ids.sort();
+            ids.dedup();
+        }
+        doc.verification_method.sort_by(|a, b| a.id.cmp(&b.id));
+        doc.service.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let value = serde_json::to_value(&doc).expect("Serializing a document cannot fail");
+        canonicalize(&value)
+    }
+
+    /// Multihash of `canonical_json`, equal for any two documents that only differ in
+    /// member or set order.
+    pub fn document_hash(&self, digest: DocumentDigest) -> Vec<u8> {
+        digest.multihash(self.canonical_json().as_bytes())
+    }
+
+    /// Encodes the document in its `application/did+cbor` representation: the data model
+    /// of the JSON representation, with maps, arrays and strings encoded as CBOR.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut cbor = vec![];
+        ciborium::ser::into_writer(self, &mut cbor).expect("Encoding a document cannot fail");
+        cbor
+    }
+
+    /// Decodes a document from its `application/did+cbor` representation; decoding the
+    /// output of `to_cbor` yields an equal document.
+    pub fn from_cbor(cbor: &[u8]) -> Result<Self, SolResolverError> {
+        ciborium::de::from_reader(cbor).map_err(|e| SolResolverError::InvalidDidDocument(e.to_string()))
+    }
+
+    /// Converts a DIDComm DIDDoc into W3C format, see `From<DIDDoc>`.
+    pub fn from_did_doc(did_doc: DIDDoc) -> Self {
+        Self::from(did_doc)
+    }
 
-        Ok(doc)
+    fn verification_type_from_string(vm_type: &str) -> VerificationMethodType {
+        match vm_type {
+            "JsonWebKey2020" => VerificationMethodType::JsonWebKey2020,
+            "X25519KeyAgreementKey2019" => VerificationMethodType::X25519KeyAgreementKey2019,
+            "Ed25519VerificationKey2018" => VerificationMethodType::Ed25519VerificationKey2018,
+            "EcdsaSecp256k1VerificationKey2019" =>
+                VerificationMethodType::EcdsaSecp256k1VerificationKey2019,
+            "X25519KeyAgreementKey2020" => VerificationMethodType::X25519KeyAgreementKey2020,
+            "Ed25519VerificationKey2020" => VerificationMethodType::Ed25519VerificationKey2020,
+            _ => VerificationMethodType::Other,
+        }
     }
 
     fn verification_type_to_string(vm_type: &didcomm::did::VerificationMethodType) -> String {
@@ -146,6 +305,38 @@ impl W3cDidDocument {
         ).to_string()
     }
 
+    fn method_type_to_string(method_type: u8) -> String {
+        (
+            match method_type {
+                METHOD_TYPE_ED25519_VERIFICATION_KEY_2018 => "Ed25519VerificationKey2018",
+                METHOD_TYPE_ECDSA_SECP256K1_RECOVERY_METHOD_2020 =>
+                    "EcdsaSecp256k1RecoveryMethod2020",
+                METHOD_TYPE_ECDSA_SECP256K1_VERIFICATION_KEY_2019 =>
+                    "EcdsaSecp256k1VerificationKey2019",
+                _ => "Other",
+            }
+        ).to_string()
+    }
+
+    // Ed25519 keys are published in base58, secp256k1 keys and Ethereum addresses in
+    // 0x-prefixed hex
+    fn method_public_key(method_type: u8, key_data: &[u8]) -> PublicKeyFormat {
+        match method_type {
+            METHOD_TYPE_ED25519_VERIFICATION_KEY_2018 =>
+                PublicKeyFormat::Base58 {
+                    public_key_base58: bs58::encode(key_data).into_string(),
+                },
+            METHOD_TYPE_ECDSA_SECP256K1_RECOVERY_METHOD_2020 =>
+                PublicKeyFormat::EthereumAddress {
+                    ethereum_address: encode_hex(key_data),
+                },
+            _ =>
+                PublicKeyFormat::Hex {
+                    public_key_hex: encode_hex(key_data),
+                },
+        }
+    }
+
     fn default_context() -> Vec<String> {
         vec![
             "https://www.w3.org/ns/did/v1".to_string(),
@@ -153,3 +344,223 @@ impl W3cDidDocument {
         ]
     }
 }
+
+impl From<W3cDidDocumentJson> for W3cDidDocument {
+    fn from(json: W3cDidDocumentJson) -> Self {
+        let mut verification_method = json.verification_method;
+        let mut references = |entries: Vec<RelationshipEntry>| -> Vec<String> {
+            entries
+                .into_iter()
+                .map(|entry| {
+                    match entry {
+                        RelationshipEntry::Reference(id) => id,
+                        RelationshipEntry::Embedded(vm) => {
+                            let id = vm.id.clone();
+                            if !verification_method.iter().any(|listed| listed.id == id) {
+                                verification_method.push(vm);
+                            }
+                            id
+                        }
+                    }
+                })
+                .collect()
+        };
+
+        let authentication = references(json.authentication);
+        let assertion_method = references(json.assertion_method);
+        let key_agreement = references(json.key_agreement);
+        let capability_invocation = references(json.capability_invocation);
+        let capability_delegation = references(json.capability_delegation);
+
+        Self {
+            context: json.context,
+            id: json.id,
+            also_known_as: json.also_known_as,
+            controller: json.controller,
+            verification_method,
+            authentication,
+            assertion_method,
+            key_agreement,
+            capability_invocation,
+            capability_delegation,
+            service: json.service,
+        }
+    }
+}
+
+/// Converts a DIDComm DIDDoc without touching the network. DIDDocs only carry the
+/// `authentication` and `keyAgreement` relationships, so the other relationships are empty.
+impl From<DIDDoc> for W3cDidDocument {
+    fn from(did_doc: DIDDoc) -> Self {
+        Self {
+            context: W3cDidDocument::default_context(),
+            id: did_doc.id,
+            also_known_as: vec![],
+            controller: vec![],
+            verification_method: did_doc.verification_method
+                .into_iter()
+                .map(|vm| VerificationMethod {
+                    id: vm.id,
+                    type_: W3cDidDocument::verification_type_to_string(&vm.type_),
+                    controller: vm.controller,
+                    public_key: match vm.verification_material {
+                        VerificationMaterial::Base58 { public_key_base58 } =>
+                            PublicKeyFormat::Base58 { public_key_base58 },
+                        VerificationMaterial::Multibase { public_key_multibase } =>
+                            PublicKeyFormat::Multibase { public_key_multibase },
+                        VerificationMaterial::JWK { public_key_jwk } =>
+                            PublicKeyFormat::Jwk { public_key_jwk },
+                    },
+                })
+                .collect(),
+            authentication: did_doc.authentication,
+            assertion_method: vec![],
+            key_agreement: did_doc.key_agreement,
+            capability_invocation: vec![],
+            capability_delegation: vec![],
+            service: did_doc.service
+                .into_iter()
+                .map(|svc| {
+                    match svc.service_endpoint {
+                        ServiceKind::DIDCommMessaging { value } =>
+                            Service {
+                                id: svc.id,
+                                type_: DIDCOMM_MESSAGING_SERVICE_TYPE.to_string(),
+                                service_endpoint: serde_json::to_value(value).unwrap_or_default(),
+                            },
+                        // `DidDocBuilder` and `TryFrom<W3cDidDocument>` keep the whole
+                        // W3C service here
+                        ServiceKind::Other { value } =>
+                            Service {
+                                id: svc.id,
+                                type_: value
+                                    .get("type")
+                                    .and_then(|t| t.as_str())
+                                    .unwrap_or("UnknownType")
+                                    .to_string(),
+                                service_endpoint: value
+                                    .get("serviceEndpoint")
+                                    .cloned()
+                                    .unwrap_or_default(),
+                            },
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Converts a W3C document into a DIDComm DIDDoc without touching the network.
+///
+/// Verification material keeps the Base58/Multibase/JWK form it was published with and
+/// hex keys become multibase like in documents resolved by `SolResolver`. Methods published
+/// as an Ethereum address or with other material are left out, along with their
+/// relationship references: an address is not a public key, and DIDComm has no material
+/// for either. Relative references such as `#key-1` are made absolute against the
+/// document id. Fails when the id is not a DID or a hex key does not decode.
+impl TryFrom<W3cDidDocument> for DIDDoc {
+    type Error = SolResolverError;
+
+    fn try_from(doc: W3cDidDocument) -> Result<Self, Self::Error> {
+        if !doc.id.starts_with("did:") {
+            return Err(SolResolverError::InvalidDidDocument(format!("{} is not a DID", doc.id)));
+        }
+        let id = doc.id;
+        let absolute = |reference: String| {
+            if reference.starts_with('#') { format!("{}{}", id, reference) } else { reference }
+        };
+
+        let mut skipped = Vec::new();
+        let mut verification_method = Vec::new();
+        for vm in doc.verification_method {
+            let verification_material = match vm.public_key {
+                PublicKeyFormat::Base58 { public_key_base58 } =>
+                    VerificationMaterial::Base58 { public_key_base58 },
+                PublicKeyFormat::Multibase { public_key_multibase } =>
+                    VerificationMaterial::Multibase { public_key_multibase },
+                PublicKeyFormat::Jwk { public_key_jwk } =>
+                    VerificationMaterial::JWK { public_key_jwk },
+                // the multibase form `DidDocBuilder` gives secp256k1 keys
+                PublicKeyFormat::Hex { public_key_hex: hex } => {
+                    let key = decode_hex(&hex).ok_or_else(||
+                        SolResolverError::InvalidDidDocument(format!("Invalid hex key of {}: {}", vm.id, hex))
+                    )?;
+                    VerificationMaterial::Multibase {
+                        public_key_multibase: format!("z{}", bs58::encode(key).into_string()),
+                    }
+                }
+                PublicKeyFormat::EthereumAddress { .. } | PublicKeyFormat::Other(_) => {
+                    skipped.push(absolute(vm.id));
+                    continue;
+                }
+            };
+
+            verification_method.push(didcomm::did::VerificationMethod {
+                id: absolute(vm.id),
+                type_: W3cDidDocument::verification_type_from_string(&vm.type_),
+                controller: absolute(vm.controller),
+                verification_material,
+            });
+        }
+        let references = |ids: Vec<String>| -> Vec<String> {
+            ids.into_iter()
+                .map(absolute)
+                .filter(|id| !skipped.contains(id))
+                .collect()
+        };
+
+        let service = doc.service
+            .into_iter()
+            .map(|svc| {
+                let id = absolute(svc.id);
+                didcomm::did::Service {
+                    id: id.clone(),
+                    service_endpoint: ServiceKind::Other {
+                        value: json!({
+                            "id": id,
+                            "type": svc.type_,
+                            "serviceEndpoint": svc.service_endpoint,
+                        }),
+                    },
+                }
+            })
+            .collect();
+
+        Ok(DIDDoc {
+            key_agreement: references(doc.key_agreement),
+            authentication: references(doc.authentication),
+            verification_method,
+            service,
+            id,
+        })
+    }
+}
+
+// `@context` and `controller` may be published either as a single string or as an array
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error> where D: Deserializer<'de> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    format!("0x{}", bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+}
+
+// Hex decoding of published keys, with an optional 0x prefix
+pub(crate) fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    (0..hex.len() / 2).map(|i| u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()).collect()
+}
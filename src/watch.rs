@@ -0,0 +1,182 @@
+use crate::did_sol::DidSol;
+use crate::errors::*;
+use crate::resolution::DocumentMetadata;
+use crate::resolver::{ DidAccountSnapshot, SolResolver };
+
+use std::time::Duration;
+use didcomm::did::DIDDoc;
+use futures::{ stream, Stream, StreamExt };
+use log::{ debug, warn };
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use solana_sdk::{ account::Account, pubkey::Pubkey };
+use tokio::sync::mpsc;
+
+type UpdateSender = mpsc::UnboundedSender<Result<DidUpdate, SolResolverError>>;
+
+// Reconnect delays after the subscription drops
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// A DID document rebuilt after its DID account changed.
+#[derive(Debug, Clone)]
+pub struct DidUpdate {
+    /// Slot of the account change
+    pub slot: u64,
+    pub did_doc: DIDDoc,
+    pub metadata: DocumentMetadata,
+}
+
+impl SolResolver {
+    /// Streams the DID document every time its DID account changes.
+    ///
+    /// Subscribes to the DID account through the network's pubsub endpoint
+    /// (`accountSubscribe`), reconnecting with backoff whenever the subscription drops, and
+    /// invalidates the resolver's cached document on every change. After every reconnect
+    /// the account is read once over RPC, so a change made while disconnected is still
+    /// reported. The subscription and its websocket are closed as soon as the stream is
+    /// dropped. The network's `headers` are not sent on the websocket, which the pubsub
+    /// client does not support. Must be called from within a Tokio runtime.
+    pub fn watch(
+        &self,
+        did: &str
+    ) -> Result<impl Stream<Item = Result<DidUpdate, SolResolverError>>, SolResolverError> {
+        let did_sol: DidSol = did.parse()?;
+        let did_pubkey = did_sol.pubkey();
+        let network = self.config().networks
+            .get(did_sol.network())
+            .ok_or_else(|| SolResolverError::UnsupportedNetwork(did_sol.network().to_string()))?;
+        if !network.headers.is_empty() {
+            warn!("Pubsub connections for {} are opened without the configured RPC headers", did);
+        }
+        let ws_url = network.websocket_url();
+        let (account_address, _) = self.derive_did_account(&did_sol);
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let resolver = self.clone();
+        let did = did.to_string();
+
+        tokio::spawn(async move {
+            let mut delay = INITIAL_RECONNECT_DELAY;
+            // hash of the last account data seen, to spot changes missed while disconnected
+            let mut data_hash = None;
+
+            while !sender.is_closed() {
+                let forwarded = resolver.forward_updates(
+                    &ws_url,
+                    &did,
+                    did_pubkey,
+                    account_address,
+                    &mut data_hash,
+                    &sender
+                ).await;
+                match forwarded {
+                    Ok(delivered) => {
+                        debug!("Subscription for {} closed", did);
+                        if delivered {
+                            delay = INITIAL_RECONNECT_DELAY;
+                        }
+                    }
+                    Err(e) => warn!("Subscription for {} failed: {}", did, e),
+                }
+
+                tokio::select! {
+                    _ = sender.closed() => break,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        });
+
+        Ok(
+            stream::unfold(receiver, |mut receiver| async move {
+                receiver.recv().await.map(|update| (update, receiver))
+            })
+        )
+    }
+
+    // Forwards rebuilt documents until the subscription ends; returns whether any were delivered
+    async fn forward_updates(
+        &self,
+        ws_url: &str,
+        did: &str,
+        did_pubkey: Pubkey,
+        account_address: Pubkey,
+        data_hash: &mut Option<String>,
+        sender: &UpdateSender
+    ) -> Result<bool, SolResolverError> {
+        let client = PubsubClient::new(ws_url).await.map_err(|e|
+            SolResolverError::RpcError(e.to_string())
+        )?;
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(self.config().commitment),
+            ..Default::default()
+        };
+        let (mut notifications, unsubscribe) = client
+            .account_subscribe(&account_address, Some(config)).await
+            .map_err(|e| SolResolverError::RpcError(e.to_string()))?;
+        debug!("Subscribed to DID account {} for {}", account_address, did);
+
+        // the first read only sets the baseline later reads are compared against
+        let mut delivered = false;
+        match self.fetch_account(did).await {
+            Ok(snapshot) => {
+                let hash = snapshot.data_hash();
+                if matches!(data_hash.replace(hash.clone()), Some(previous) if previous != hash) {
+                    delivered = self.send_update(did, &snapshot, sender);
+                }
+            }
+            Err(e) => warn!("Could not read DID account of {} after subscribing: {}", did, e),
+        }
+
+        loop {
+            let response = tokio::select! {
+                _ = sender.closed() => break,
+                response = notifications.next() => match response {
+                    Some(response) => response,
+                    None => break,
+                },
+            };
+
+            // a closed DID account reverts to the generative document
+            let account = response.value
+                .decode::<Account>()
+                .filter(|account| account.lamports > 0);
+            let snapshot = DidAccountSnapshot {
+                did: did.to_string(),
+                did_pubkey,
+                account_address,
+                owner: account.as_ref().map(|account| account.owner),
+                data: account.map(|account| account.data),
+                slot: response.context.slot,
+            };
+
+            *data_hash = Some(snapshot.data_hash());
+            if !self.send_update(did, &snapshot, sender) {
+                break;
+            }
+            delivered = true;
+        }
+
+        drop(notifications);
+        unsubscribe().await;
+        let _ = client.shutdown().await;
+
+        Ok(delivered)
+    }
+
+    // Invalidates the cached document and sends the rebuilt one; returns false once the
+    // stream was dropped
+    fn send_update(&self, did: &str, snapshot: &DidAccountSnapshot, sender: &UpdateSender) -> bool {
+        self.invalidate(did);
+        let update = self.build_document(snapshot).map(|resolution| DidUpdate {
+            slot: snapshot.slot,
+            did_doc: resolution.did_doc,
+            metadata: resolution.metadata,
+        });
+
+        sender.send(update).is_ok()
+    }
+}
@@ -0,0 +1,143 @@
+use crate::errors::*;
+use crate::w3c_doc::W3cDidDocument;
+
+use async_trait::async_trait;
+use didcomm::did::{ DIDDoc, DIDResolver };
+use log::{ info, debug };
+use percent_encoding::percent_decode_str;
+use reqwest::Url;
+
+/// Resolver for did:web documents published over HTTPS.
+pub struct WebResolver {
+    client: reqwest::Client,
+    scheme: String,
+}
+
+impl Default for WebResolver {
+    fn default() -> Self {
+        Self::new(reqwest::Client::new())
+    }
+}
+
+impl WebResolver {
+    /// Creates a resolver that fetches documents with the given HTTP client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            scheme: "https".to_string(),
+        }
+    }
+
+    /// Overrides the URL scheme, e.g. "http" when resolving against a local test server.
+    pub fn with_scheme(mut self, scheme: &str) -> Self {
+        self.scheme = scheme.to_string();
+        self
+    }
+
+    /// Maps a did:web identifier to the URL of its DID document.
+    ///
+    /// `did:web:example.com` maps to `https://example.com/.well-known/did.json` and
+    /// `did:web:example.com:user:alice` maps to `https://example.com/user/alice/did.json`.
+    /// Every segment is percent-decoded, so `did:web:localhost%3A8443` names port 8443.
+    pub fn document_url(&self, did: &str) -> Result<String, SolResolverError> {
+        let method_specific_id = did
+            .strip_prefix("did:web:")
+            .ok_or(SolResolverError::InvalidDidWebFormat)?;
+
+        let segments = method_specific_id
+            .split(':')
+            .map(decode_segment)
+            .collect::<Result<Vec<_>, _>>()?;
+        let (domain, path) = segments.split_first().ok_or(SolResolverError::InvalidDidWebFormat)?;
+
+        // the domain may carry a port, but nothing that would move the URL's authority or path
+        let mut url = Url::parse(&format!("{}://{}", self.scheme, domain)).map_err(|_|
+            SolResolverError::InvalidDidWebFormat
+        )?;
+        if
+            url.host_str().is_none() ||
+            url.path() != "/" ||
+            !url.username().is_empty() ||
+            url.password().is_some() ||
+            url.query().is_some() ||
+            url.fragment().is_some()
+        {
+            return Err(SolResolverError::InvalidDidWebFormat);
+        }
+
+        let path: Vec<&str> = if path.is_empty() {
+            vec![".well-known"]
+        } else {
+            path.iter().map(String::as_str).collect()
+        };
+        url.path_segments_mut()
+            .map_err(|_| SolResolverError::InvalidDidWebFormat)?
+            .pop_if_empty()
+            .extend(path)
+            .push("did.json");
+
+        Ok(url.to_string())
+    }
+
+    /// Fetches the W3C DID document for a did:web identifier.
+    ///
+    /// Returns `Ok(None)` when the host answers 404 Not Found.
+    pub async fn resolve_w3c(&self, did: &str) -> Result<Option<W3cDidDocument>, SolResolverError> {
+        let url = self.document_url(did)?;
+        debug!("Fetching did:web document from {}", url);
+
+        let response = self.client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "application/did+json, application/json")
+            .send().await
+            .map_err(|e| SolResolverError::HttpError(e.to_string()))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let body = response
+            .error_for_status()
+            .map_err(|e| SolResolverError::HttpError(e.to_string()))?
+            .text().await
+            .map_err(|e| SolResolverError::HttpError(e.to_string()))?;
+
+        let document: W3cDidDocument = serde_json
+            ::from_str(&body)
+            .map_err(|e| SolResolverError::InvalidDidDocument(e.to_string()))?;
+
+        if document.id != did {
+            return Err(SolResolverError::DocumentIdMismatch);
+        }
+
+        Ok(Some(document))
+    }
+}
+
+#[async_trait(?Send)]
+impl DIDResolver for WebResolver {
+    async fn resolve(
+        &self,
+        did: &str
+    ) -> std::result::Result<Option<DIDDoc>, didcomm::error::Error> {
+        info!("Resolving DID: {}", did);
+
+        match self.resolve_w3c(did).await? {
+            Some(document) => Ok(Some(DIDDoc::try_from(document)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+// Percent-decodes one colon-separated segment of a did:web identifier; a segment may not
+// be empty or decode to a path separator or relative path
+fn decode_segment(segment: &str) -> Result<String, SolResolverError> {
+    let decoded = percent_decode_str(segment)
+        .decode_utf8()
+        .map_err(|_| SolResolverError::InvalidDidWebFormat)?;
+    if decoded.is_empty() || decoded.contains('/') || decoded == "." || decoded == ".." {
+        return Err(SolResolverError::InvalidDidWebFormat);
+    }
+
+    Ok(decoded.into_owned())
+}
@@ -0,0 +1,104 @@
+use std::str::FromStr;
+use anchor_client::anchor_lang::AccountSerialize;
+use didcomm_soldid_resolver::{ DidSol, SolResolver, SolResolverError };
+use didcomm_soldid_resolver::resolver::DidAccountSnapshot;
+use sol_did::state::{ DidAccount, VerificationMethod };
+use solana_sdk::pubkey::Pubkey;
+
+const DID: &str = "did:sol:devnet:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+
+fn snapshot(resolver: &SolResolver, bump_offset: u8) -> DidAccountSnapshot {
+    let did_pubkey = Pubkey::from_str("BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS").unwrap();
+    let (account_address, bump) = resolver.derive_did_account(&DidSol::new("devnet", did_pubkey));
+
+    let account = DidAccount {
+        version: 0,
+        bump: bump.wrapping_add(bump_offset),
+        nonce: 0,
+        initial_verification_method: VerificationMethod {
+            fragment: "default".to_string(),
+            flags: 1 << 6,
+            method_type: 0,
+            key_data: did_pubkey.to_bytes().to_vec(),
+        },
+        verification_methods: vec![],
+        services: vec![],
+        native_controllers: vec![],
+        other_controllers: vec![],
+    };
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+
+    DidAccountSnapshot {
+        did: DID.to_string(),
+        did_pubkey,
+        account_address,
+        data: Some(data),
+        owner: Some(resolver.config().program_id()),
+        slot: 1,
+    }
+}
+
+#[test]
+fn test_valid_account_is_decoded() {
+    let resolver = SolResolver::default();
+    let account = resolver.decode_account(&snapshot(&resolver, 0)).unwrap().unwrap();
+    assert_eq!(account.initial_verification_method.fragment, "default");
+
+    let resolution = resolver.build_document(&snapshot(&resolver, 0)).unwrap();
+    assert_eq!(resolution.did_doc.id, DID);
+}
+
+#[test]
+fn test_chainless_snapshot_has_no_account() {
+    let resolver = SolResolver::default();
+    let snapshot = DidAccountSnapshot { data: None, owner: None, ..snapshot(&resolver, 0) };
+    assert!(resolver.decode_account(&snapshot).unwrap().is_none());
+}
+
+#[test]
+fn test_rejects_foreign_owner() {
+    let resolver = SolResolver::default();
+    let attacker = Pubkey::new_unique();
+    let snapshot = DidAccountSnapshot { owner: Some(attacker), ..snapshot(&resolver, 0) };
+
+    match resolver.build_document(&snapshot) {
+        Err(SolResolverError::InvalidAccountOwner(owner)) => assert_eq!(owner, attacker),
+        other => panic!("expected an owner error, got {:?}", other.map(|r| r.did_doc.id)),
+    }
+}
+
+#[test]
+fn test_rejects_wrong_discriminator() {
+    let resolver = SolResolver::default();
+    let mut snapshot = snapshot(&resolver, 0);
+    snapshot.data.as_mut().unwrap()[0] ^= 0xff;
+
+    assert!(
+        matches!(resolver.decode_account(&snapshot), Err(SolResolverError::InvalidAccountDiscriminator))
+    );
+}
+
+#[test]
+fn test_rejects_out_of_bounds_length() {
+    let resolver = SolResolver::default();
+    let mut snapshot = snapshot(&resolver, 0);
+    snapshot.data.as_mut().unwrap().truncate(20);
+
+    assert!(matches!(resolver.decode_account(&snapshot), Err(SolResolverError::InvalidAccountSize(20))));
+}
+
+#[test]
+fn test_rejects_bump_mismatch() {
+    let resolver = SolResolver::default();
+    let snapshot = snapshot(&resolver, 1);
+    let (_, bump) = resolver.derive_did_account(&DID.parse().unwrap());
+
+    match resolver.decode_account(&snapshot) {
+        Err(SolResolverError::BumpMismatch { expected, found }) => {
+            assert_eq!(expected, bump);
+            assert_eq!(found, bump.wrapping_add(1));
+        }
+        other => panic!("expected a bump mismatch, got {:?}", other.map(|a| a.map(|a| a.bump))),
+    }
+}
@@ -0,0 +1,95 @@
+mod mock_rpc;
+
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use didcomm_soldid_resolver::did_sol::DidSolParseError;
+use didcomm_soldid_resolver::{ SolResolver, SolResolverConfig, SolResolverError };
+use mock_rpc::start_mock_rpc;
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+static BATCH_CALLS: AtomicUsize = AtomicUsize::new(0);
+static LARGEST_BATCH: AtomicUsize = AtomicUsize::new(0);
+
+#[tokio::test]
+async fn test_resolve_many_batches_per_network() {
+    let localnet_rpc = start_mock_rpc(|method, params| {
+        assert_eq!(method, "getMultipleAccounts");
+        let keys = params[0].as_array().unwrap().len();
+        BATCH_CALLS.fetch_add(1, Ordering::SeqCst);
+        LARGEST_BATCH.fetch_max(keys, Ordering::SeqCst);
+        Ok(json!({ "context": { "slot": 7 }, "value": vec![serde_json::Value::Null; keys] }))
+    }).await;
+    let devnet_rpc = start_mock_rpc(|_, _| Err(500)).await;
+
+    let mut config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &localnet_rpc)
+        .with_rpc_url("devnet", &devnet_rpc);
+    config.networks.remove("testnet");
+    let resolver = SolResolver::new(config);
+
+    let localnet_dids: Vec<String> = (0..250)
+        .map(|_| format!("did:sol:localnet:{}", Pubkey::new_unique()))
+        .collect();
+    let devnet_did = format!("did:sol:devnet:{}", Pubkey::new_unique());
+    let testnet_did = format!("did:sol:testnet:{}", Pubkey::new_unique());
+
+    let mut dids: Vec<&str> = localnet_dids.iter().map(String::as_str).collect();
+    dids.extend([devnet_did.as_str(), testnet_did.as_str(), "did:sol:not-a-key"]);
+
+    let results = resolver.resolve_many(&dids).await;
+    assert_eq!(results.len(), 253);
+
+    // 250 localnet DIDs take three getMultipleAccounts calls
+    assert_eq!(BATCH_CALLS.load(Ordering::SeqCst), 3);
+    assert_eq!(LARGEST_BATCH.load(Ordering::SeqCst), 100);
+    for did in &localnet_dids {
+        let resolution = results[did].as_ref().unwrap();
+        assert_eq!(resolution.did_doc.id, *did);
+        assert_eq!(resolution.did_doc.verification_method.len(), 1);
+        assert_eq!(resolution.metadata.slot, Some(7));
+    }
+
+    assert!(matches!(results[&devnet_did], Err(SolResolverError::RpcError(_))));
+    assert!(matches!(results[&testnet_did], Err(SolResolverError::UnsupportedNetwork(_))));
+    assert!(matches!(results["did:sol:not-a-key"], Err(SolResolverError::InvalidDid(DidSolParseError::InvalidBase58(_)))));
+}
+
+#[tokio::test]
+async fn test_resolve_many_strict_reports_missing_accounts() {
+    let rpc_url = start_mock_rpc(|_, params| {
+        let keys = params[0].as_array().unwrap().len();
+        Ok(json!({ "context": { "slot": 1 }, "value": vec![serde_json::Value::Null; keys] }))
+    }).await;
+    let config = SolResolverConfig::default().with_rpc_url("localnet", &rpc_url).with_strict(true);
+    let resolver = SolResolver::new(config);
+
+    let did = format!("did:sol:localnet:{}", Pubkey::new_unique());
+    let results = resolver.resolve_many(&[did.as_str()]).await;
+
+    assert!(matches!(results[&did], Err(SolResolverError::NotFound)));
+}
+
+#[tokio::test]
+async fn test_resolve_many_reports_accounts_missing_from_response() {
+    // a node that drops the last account of the batch
+    let rpc_url = start_mock_rpc(|_, params| {
+        let keys = params[0].as_array().unwrap().len();
+        Ok(json!({ "context": { "slot": 1 }, "value": vec![serde_json::Value::Null; keys - 1] }))
+    }).await;
+    let config = SolResolverConfig::default().with_rpc_url("localnet", &rpc_url);
+    let resolver = SolResolver::new(config);
+
+    let dids: Vec<String> = (0..3).map(|_| format!("did:sol:localnet:{}", Pubkey::new_unique())).collect();
+    let dids: Vec<&str> = dids.iter().map(String::as_str).collect();
+    let results = resolver.resolve_many(&dids).await;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results.values().filter(|result| result.is_ok()).count(), 2);
+    assert_eq!(
+        results
+            .values()
+            .filter(|result| matches!(result, Err(SolResolverError::RpcError(_))))
+            .count(),
+        1
+    );
+}
@@ -0,0 +1,146 @@
+use std::str::FromStr;
+use didcomm_soldid_resolver::canonical::canonicalize;
+use didcomm_soldid_resolver::{ DidSol, DocumentDigest, W3cDidDocument };
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+const KEY: &str = "BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+
+#[test]
+#[allow(clippy::excessive_precision)]
+fn test_canonicalizes_rfc8785_example() {
+    let value =
+        json!({
+        "numbers": [333333333.33333329, 1e30, 4.50, 2e-3, 0.000000000000000000000000001],
+        "string": "\u{20ac}$\u{f}\nA'B\"\\\\\"/",
+        "literals": [null, true, false]
+    });
+
+    assert_eq!(
+        canonicalize(&value),
+        r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+}
+
+#[test]
+fn test_canonicalizes_numbers_like_ecmascript() {
+    let cases = [
+        (json!(0.0), "0"),
+        (json!(-0.0), "0"),
+        (json!(5e-324), "5e-324"),
+        (json!(1.7976931348623157e308), "1.7976931348623157e+308"),
+        (json!(9007199254740992u64), "9007199254740992"),
+        (json!(1e21), "1e+21"),
+        (json!(1e20), "100000000000000000000"),
+        (json!(0.000001), "0.000001"),
+        (json!(1e-7), "1e-7"),
+        (json!(-42), "-42"),
+    ];
+
+    for (value, expected) in cases {
+        assert_eq!(canonicalize(&value), expected);
+    }
+}
+
+#[test]
+fn test_sorts_members_by_utf16_code_units() {
+    let value =
+        json!({
+        "\u{20ac}": "Euro Sign",
+        "\r": "Carriage Return",
+        "\u{fb33}": "Hebrew Letter Dalet With Dagesh",
+        "1": "One",
+        "\u{1f600}": "Emoji: Grinning Face",
+        "\u{80}": "Control",
+        "\u{f6}": "Latin Small Letter O With Diaeresis"
+    });
+    let canonical = canonicalize(&value);
+
+    let order = [
+        "Carriage Return",
+        "One",
+        "Control",
+        "Latin Small Letter O With Diaeresis",
+        "Euro Sign",
+        "Emoji: Grinning Face",
+        "Hebrew Letter Dalet With Dagesh",
+    ];
+    let positions: Vec<usize> = order
+        .iter()
+        .map(|name| canonical.find(name).unwrap())
+        .collect();
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+fn document() -> W3cDidDocument {
+    let value =
+        json!({
+        "@context": ["https://w3id.org/did/v1.0", "https://w3id.org/sol/v3.0"],
+        "id": format!("did:sol:{}", KEY),
+        "verificationMethod": [
+            {
+                "id": format!("did:sol:{}#default", KEY),
+                "type": "Ed25519VerificationKey2018",
+                "controller": format!("did:sol:{}", KEY),
+                "publicKeyBase58": KEY
+            },
+            {
+                "id": format!("did:sol:{}#key-2", KEY),
+                "type": "Ed25519VerificationKey2018",
+                "controller": format!("did:sol:{}", KEY),
+                "publicKeyBase58": "JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr"
+            }
+        ],
+        "authentication": [format!("did:sol:{}#default", KEY), format!("did:sol:{}#key-2", KEY)],
+        "capabilityInvocation": [format!("did:sol:{}#default", KEY)]
+    });
+
+    serde_json::from_value(value).unwrap()
+}
+
+#[test]
+fn test_document_hash_ignores_set_order() {
+    let doc = document();
+    let mut reordered = doc.clone();
+    reordered.verification_method.reverse();
+    reordered.authentication.reverse();
+
+    assert_ne!(serde_json::to_string(&doc).unwrap(), serde_json::to_string(&reordered).unwrap());
+    assert_eq!(doc.canonical_json(), reordered.canonical_json());
+    assert_eq!(
+        doc.document_hash(DocumentDigest::Sha256),
+        reordered.document_hash(DocumentDigest::Sha256)
+    );
+}
+
+#[test]
+fn test_document_hash_is_a_multihash() {
+    let doc = document();
+
+    let sha256 = doc.document_hash(DocumentDigest::Sha256);
+    assert_eq!(&sha256[..2], &[0x12, 0x20]);
+    assert_eq!(sha256.len(), 34);
+
+    let sha512 = doc.document_hash(DocumentDigest::Sha512);
+    assert_eq!(&sha512[..2], &[0x13, 0x40]);
+    assert_eq!(sha512.len(), 66);
+}
+
+#[test]
+fn test_document_hash_changes_with_content() {
+    let doc = document();
+    let mut changed = doc.clone();
+    changed.key_agreement.push(format!("did:sol:{}#key-2", KEY));
+
+    assert_ne!(
+        doc.document_hash(DocumentDigest::default()),
+        changed.document_hash(DocumentDigest::default())
+    );
+
+    let did = DidSol::mainnet(Pubkey::from_str(KEY).unwrap());
+    let generative = W3cDidDocument::from_did_account(&did, None);
+    assert_ne!(
+        doc.document_hash(DocumentDigest::default()),
+        generative.document_hash(DocumentDigest::default())
+    );
+}
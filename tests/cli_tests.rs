@@ -0,0 +1,156 @@
+#![cfg(feature = "cli")]
+
+mod mock_rpc;
+
+use std::process::Command;
+use std::str::FromStr;
+use base64::{ engine::general_purpose::STANDARD, Engine };
+use didcomm_soldid_resolver::{ DidRegistrar, DidSol, SolResolver, SolResolverConfig };
+use mock_rpc::{ start_mock_rpc, missing_account };
+use serde_json::{ json, Value };
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+
+const KEY: &str = "2CE5VrAVc51cGCwk8JScajgpR8RuKmV1vxLPUpM8Lkxv";
+
+fn soldid(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_soldid")).args(args).output().expect("failed to run soldid")
+}
+
+#[test]
+fn test_cli_pda() {
+    let key = KEY;
+    let output = soldid(&["pda", key, "--network", "devnet", "--output", "json-compact"]);
+    assert!(output.status.success());
+
+    let pda: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let (address, bump) = SolResolver::default()
+        .derive_did_account(&DidSol::new("devnet", Pubkey::from_str(key).unwrap()));
+    assert_eq!(pda["did"], format!("did:sol:devnet:{}", key));
+    assert_eq!(pda["address"], address.to_string());
+    assert_eq!(pda["bump"], bump);
+    assert_eq!(pda["programId"], "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc");
+}
+
+#[test]
+fn test_cli_pda_with_program_id() {
+    let key = KEY;
+    let program_id = "6wciiqLfbXB1WocZsDBXE2xMLJNNFxnyL6Mvo91XjSkR";
+    let output = soldid(&["pda", key, "--program-id", program_id]);
+    assert!(output.status.success());
+
+    let pda: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(pda["did"], format!("did:sol:{}", key));
+    assert_eq!(pda["programId"], program_id);
+}
+
+#[test]
+fn test_cli_reads_config_file() {
+    let key = KEY;
+    let program_id = "6wciiqLfbXB1WocZsDBXE2xMLJNNFxnyL6Mvo91XjSkR";
+    let path = std::env::temp_dir().join(format!("soldid-cli-{}.toml", std::process::id()));
+    std::fs::write(&path, format!("program_id = \"{}\"\n", program_id)).unwrap();
+
+    let output = soldid(&["pda", key, "--config", path.to_str().unwrap()]);
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success());
+
+    let pda: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(pda["programId"], program_id);
+
+    let output = soldid(&["pda", key, "--config", "/nonexistent/soldid.toml"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_rejects_invalid_did() {
+    let output = soldid(&["resolve", "did:sol:not-a-key"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid DID format"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli_resolve_against_rpc() {
+    let rpc_url = start_mock_rpc(|method, _| {
+        assert_eq!(method, "getAccountInfo");
+        Ok(missing_account(7))
+    }).await;
+
+    let output = soldid(&["resolve", KEY, "--network", "localnet", "--rpc-url", &rpc_url]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let document: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["id"], format!("did:sol:localnet:{}", KEY));
+    assert_eq!(document["verificationMethod"][0]["id"], format!("did:sol:localnet:{}#default", KEY));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli_account_against_rpc() {
+    let rpc_url = start_mock_rpc(|_, _| Ok(missing_account(7))).await;
+
+    let output = soldid(&["account", KEY, "--network", "localnet", "--rpc-url", &rpc_url]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let dump: Value = serde_json::from_slice(&output.stdout).unwrap();
+    let (address, _) = SolResolver::default()
+        .derive_did_account(&DidSol::new("localnet", Pubkey::from_str(KEY).unwrap()));
+    assert_eq!(dump["address"], address.to_string());
+    assert_eq!(dump["slot"], 7);
+    assert!(dump["account"].is_null());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cli_history_decodes_instructions() {
+    let rpc_url = start_mock_rpc(|method, _| {
+        match method {
+            "getSignaturesForAddress" =>
+                Ok(
+                    json!([{
+                        "signature": Signature::default().to_string(),
+                        "slot": 11,
+                        "err": null,
+                        "memo": null,
+                        "blockTime": 1700000000,
+                        "confirmationStatus": "finalized"
+                    }])
+                ),
+            "getTransaction" => {
+                let did = DidSol::new("localnet", Pubkey::from_str(KEY).unwrap());
+                let registrar = DidRegistrar::from_config(did.clone(), &SolResolverConfig::default());
+                let instruction = registrar.add_service(
+                    &did.pubkey(),
+                    "agent",
+                    "DIDCommMessaging",
+                    "https://agent.example.com",
+                    false
+                );
+                let transaction = registrar.transaction(&[instruction], &did.pubkey());
+                let encoded = STANDARD.encode(bincode::serialize(&transaction).unwrap());
+                Ok(
+                    json!({
+                        "slot": 11,
+                        "blockTime": 1700000000,
+                        "transaction": { "transaction": [encoded, "base64"], "meta": null }
+                    })
+                )
+            }
+            _ => Err(500),
+        }
+    }).await;
+
+    let output = soldid(&["history", KEY, "--network", "localnet", "--rpc-url", &rpc_url]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let history: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(history[0]["slot"], 11);
+    assert_eq!(history[0]["failed"], false);
+    assert_eq!(
+        history[0]["changes"],
+        json!([{
+            "instruction": "addService",
+            "fragment": "agent",
+            "serviceType": "DIDCommMessaging",
+            "serviceEndpoint": "https://agent.example.com"
+        }])
+    );
+}
@@ -0,0 +1,103 @@
+mod mock_rpc;
+
+use didcomm_soldid_resolver::{ DidSol, ResolveOptions, RetryPolicy, SolResolver, SolResolverConfig };
+use mock_rpc::{ missing_account, start_mock_rpc };
+use serde_json::Value;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::atomic::{ AtomicU64, Ordering };
+use std::time::Duration;
+
+const DID: &str = "did:sol:localnet:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+
+// answers at the slot it was asked for, echoing the commitment back in the slot's last digit
+fn echo_request(_: &str, params: &Value) -> Result<Value, u16> {
+    let commitment = match params[1]["commitment"].as_str() {
+        Some("processed") => 1,
+        Some("confirmed") => 2,
+        Some("finalized") => 3,
+        _ => 0,
+    };
+    let min_slot = params[1]["minContextSlot"].as_u64().unwrap_or(0);
+    Ok(missing_account(min_slot * 10 + commitment))
+}
+
+#[tokio::test]
+async fn test_configured_commitment_and_min_context_slot() {
+    let rpc_url = start_mock_rpc(echo_request).await;
+
+    let resolver = SolResolver::new(SolResolverConfig::default().with_rpc_url("localnet", &rpc_url));
+    let resolution = resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(resolution.metadata.slot, Some(3));
+
+    let config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &rpc_url)
+        .with_commitment(CommitmentConfig::confirmed())
+        .with_min_context_slot(5);
+    let resolution = SolResolver::new(config).resolve_document(DID).await.unwrap();
+    assert_eq!(resolution.metadata.slot, Some(52));
+}
+
+#[tokio::test]
+async fn test_per_call_overrides() {
+    let rpc_url = start_mock_rpc(echo_request).await;
+    let config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &rpc_url)
+        .with_cache_ttl(Duration::from_secs(60));
+    let resolver = SolResolver::new(config);
+
+    let options = ResolveOptions::default().with_commitment(CommitmentConfig::processed());
+    let resolution = resolver.resolve_document_with(DID, &options).await.unwrap();
+    assert_eq!(resolution.metadata.slot, Some(1));
+
+    // a read that has to include a recent update skips cached documents from before it
+    assert_eq!(resolver.resolve_document(DID).await.unwrap().metadata.slot, Some(3));
+    let options = ResolveOptions::default().with_min_context_slot(7);
+    let resolution = resolver.resolve_document_with(DID, &options).await.unwrap();
+    assert_eq!(resolution.metadata.slot, Some(73));
+}
+
+#[tokio::test]
+async fn test_cache_evicts_oldest_documents_beyond_capacity() {
+    // answers every read at the next slot, so the slot tells reads apart
+    static READS: AtomicU64 = AtomicU64::new(0);
+    let rpc_url = start_mock_rpc(|_, _| Ok(missing_account(READS.fetch_add(1, Ordering::SeqCst) + 1))).await;
+    let config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &rpc_url)
+        .with_cache_ttl(Duration::from_secs(60))
+        .with_cache_capacity(2);
+    let resolver = SolResolver::new(config);
+
+    let dids: Vec<String> = (0..3)
+        .map(|_| DidSol::new("localnet", Pubkey::new_unique()).to_string())
+        .collect();
+    for (did, slot) in dids.iter().zip(1..) {
+        assert_eq!(resolver.resolve_document(did).await.unwrap().metadata.slot, Some(slot));
+    }
+
+    // the newest documents stay cached and the oldest was dropped for them
+    assert_eq!(resolver.resolve_document(&dids[2]).await.unwrap().metadata.slot, Some(3));
+    assert_eq!(resolver.resolve_document(&dids[0]).await.unwrap().metadata.slot, Some(4));
+}
+
+#[tokio::test]
+async fn test_node_behind_min_context_slot_is_skipped() {
+    // ignores minContextSlot and answers from an old slot
+    let stale_url = start_mock_rpc(|_, _| Ok(missing_account(4))).await;
+    let current_url = start_mock_rpc(echo_request).await;
+    let config = SolResolverConfig::default()
+        .with_rpc_urls("localnet", &[&stale_url, &current_url])
+        .with_retry_policy(RetryPolicy { initial_backoff: Duration::from_millis(1), ..Default::default() });
+    let resolver = SolResolver::new(config);
+
+    let options = ResolveOptions::default().with_min_context_slot(9);
+    let snapshot = resolver.fetch_account_with(DID, &options).await.unwrap();
+    assert_eq!(snapshot.slot, 93);
+
+    let stale = resolver
+        .endpoint_health()
+        .into_iter()
+        .find(|health| health.url == stale_url)
+        .unwrap();
+    assert_eq!(stale.total_failures, 1);
+}
@@ -0,0 +1,150 @@
+use std::str::FromStr;
+use std::time::Duration;
+use didcomm_soldid_resolver::{ SolResolverConfig, SolResolverError };
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+const PROGRAM_ID: &str = "6wciiqLfbXB1WocZsDBXE2xMLJNNFxnyL6Mvo91XjSkR";
+
+fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn assert_invalid(result: Result<SolResolverConfig, SolResolverError>) {
+    assert!(matches!(result, Err(SolResolverError::InvalidConfig(_))), "{:?}", result.map(|_| ()));
+}
+
+#[test]
+fn test_from_toml() {
+    let config = SolResolverConfig::from_toml_str(
+        &format!(
+            r#"
+            program_id = "{PROGRAM_ID}"
+            commitment = "confirmed"
+            strict = true
+            timeout_secs = 10
+
+            [cache]
+            ttl_secs = 60
+            max_age_secs = 30
+            capacity = 100
+
+            [networks.mainnet]
+            rpc_urls = ["https://rpc.example.com", "https://api.mainnet-beta.solana.com"]
+            headers = {{ "x-api-key" = "secret" }}
+
+            [networks.acme]
+            rpc_urls = ["http://10.0.0.5:8899"]
+            ws_url = "ws://10.0.0.5:8900"
+            program_id = "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc"
+            "#
+        )
+    ).unwrap();
+
+    assert_eq!(config.program_id, Some(Pubkey::from_str(PROGRAM_ID).unwrap()));
+    assert_eq!(config.commitment, CommitmentConfig::confirmed());
+    assert!(config.strict);
+    assert_eq!(config.timeout, Duration::from_secs(10));
+    assert_eq!(config.cache_ttl, Some(Duration::from_secs(60)));
+    assert_eq!(config.cache_max_age, Duration::from_secs(30));
+    assert_eq!(config.cache_capacity, 100);
+
+    let mainnet = &config.networks["mainnet"];
+    assert_eq!(mainnet.rpc_urls.len(), 2);
+    assert_eq!(mainnet.headers["x-api-key"], "secret");
+
+    let acme = &config.networks["acme"];
+    assert_eq!(acme.websocket_url(), "ws://10.0.0.5:8900");
+    assert_eq!(config.network_program_id("acme").to_string(), "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc");
+
+    // networks not named in the file keep their defaults
+    assert_eq!(config.networks["devnet"].rpc_url(), "https://api.devnet.solana.com");
+}
+
+#[test]
+fn test_env_overrides() {
+    let config = SolResolverConfig::from_toml_str("commitment = \"confirmed\"")
+        .unwrap()
+        .with_env_vars(
+            env(
+                &[
+                    ("SOLDID_COMMITMENT", "processed"),
+                    ("SOLDID_STRICT", "true"),
+                    ("SOLDID_CACHE_TTL_SECS", "5"),
+                    ("SOLDID_CACHE_CAPACITY", "20"),
+                    ("SOLDID_NETWORK_DEVNET_RPC_URLS", "https://a.example, https://b.example"),
+                    ("SOLDID_NETWORK_DEVNET_HEADER_AUTHORIZATION", "Bearer token"),
+                    ("SOLDID_NETWORK_ACME_PRIVATE_RPC_URLS", "http://10.0.0.5:8899"),
+                    ("SOLDID_NETWORK_ACME_PRIVATE_PROGRAM_ID", PROGRAM_ID),
+                    ("SOLDID_CONFIG", "/etc/soldid.toml"),
+                    ("PATH", "/usr/bin"),
+                ]
+            )
+        )
+        .unwrap();
+
+    assert_eq!(config.commitment, CommitmentConfig::processed());
+    assert!(config.strict);
+    assert_eq!(config.cache_ttl, Some(Duration::from_secs(5)));
+    assert_eq!(config.cache_capacity, 20);
+    assert_eq!(config.networks["devnet"].rpc_urls, vec!["https://a.example", "https://b.example"]);
+    assert_eq!(config.networks["devnet"].headers["authorization"], "Bearer token");
+    assert_eq!(config.network_program_id("acme_private").to_string(), PROGRAM_ID);
+}
+
+#[test]
+fn test_load_reads_file() {
+    let path = std::env::temp_dir().join(format!("soldid-config-{}.toml", std::process::id()));
+    std::fs::write(&path, "strict = true\n").unwrap();
+
+    let config = SolResolverConfig::from_toml_file(&path).unwrap();
+    assert!(config.strict);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_invalid(SolResolverConfig::from_toml_file(&path));
+}
+
+#[test]
+fn test_validation() {
+    assert_invalid(SolResolverConfig::from_toml_str("program_id = \"not-a-key\""));
+    assert_invalid(SolResolverConfig::from_toml_str("commitment = \"max\""));
+    assert_invalid(SolResolverConfig::from_toml_str("timeout_secs = 0"));
+    assert_invalid(SolResolverConfig::from_toml_str("unknown = 1"));
+    assert_invalid(SolResolverConfig::from_toml_str("[networks.acme]\nws_url = \"ws://10.0.0.5:8900\""));
+    assert_invalid(SolResolverConfig::from_toml_str("[networks.acme]\nrpc_urls = []"));
+    assert_invalid(SolResolverConfig::from_toml_str("[networks.acme]\nrpc_urls = [\"ftp://host\"]"));
+    assert_invalid(SolResolverConfig::from_toml_str("[networks.\"Acme Net\"]\nrpc_urls = [\"http://host\"]"));
+    assert_invalid(
+        SolResolverConfig::from_toml_str(
+            "[networks.mainnet]\nws_url = \"https://host\""
+        )
+    );
+    assert_invalid(
+        SolResolverConfig::from_toml_str(
+            "[networks.mainnet]\nheaders = { \"bad header\" = \"x\" }"
+        )
+    );
+
+    assert_invalid(SolResolverConfig::default().with_env_vars(env(&[("SOLDID_STRICT", "maybe")])));
+}
+
+#[test]
+fn test_unknown_env_vars_are_ignored() {
+    let config = SolResolverConfig::default()
+        .with_env_vars(
+            env(
+                &[
+                    ("SOLDID_TIMEOUT", "10"),
+                    ("SOLDID_NETWORK_DEVNET_RPC", "x"),
+                    ("SOLDID_STRICT", "true"),
+                ]
+            )
+        )
+        .unwrap();
+
+    assert!(config.strict);
+    assert_eq!(config.timeout, SolResolverConfig::default().timeout);
+    assert_eq!(config.networks["devnet"].rpc_urls, SolResolverConfig::default().networks["devnet"].rpc_urls);
+}
@@ -0,0 +1,174 @@
+use std::fs;
+use std::path::Path;
+use anchor_client::anchor_lang::{ AccountDeserialize, AccountSerialize };
+use didcomm_soldid_resolver::{ DidSol, SolResolver, W3cDidDocument };
+use didcomm_soldid_resolver::resolver::DidAccountSnapshot;
+use serde_json::{ Map, Value };
+use sol_did::state::DidAccount;
+
+// Relationship and controller arrays whose order carries no meaning
+const UNORDERED: [&str; 7] = [
+    "controller",
+    "authentication",
+    "assertionMethod",
+    "keyAgreement",
+    "capabilityInvocation",
+    "capabilityDelegation",
+    "alsoKnownAs",
+];
+
+// Brings a document into a canonical form: single contexts and controllers turned into
+// arrays, unordered arrays sorted and Ethereum addresses compared without their EIP-55
+// checksum casing. Empty arrays are kept, the client always emits them
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Object(object) => {
+            let mut normalized = Map::new();
+            for (key, value) in object {
+                let value = match (key.as_str(), value) {
+                    ("ethereumAddress", Value::String(address)) => Value::String(address.to_lowercase()),
+                    ("@context", Value::String(context)) => Value::Array(vec![Value::String(context)]),
+                    (key, Value::String(value)) if UNORDERED.contains(&key) => {
+                        Value::Array(vec![Value::String(value)])
+                    }
+                    (key, Value::Array(mut values)) if UNORDERED.contains(&key) => {
+                        values.sort_by_key(|value| value.to_string());
+                        Value::Array(values)
+                    }
+                    (_, value) => normalize(value),
+                };
+                normalized.insert(key, value);
+            }
+            Value::Object(normalized)
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(normalize).collect()),
+        value => value,
+    }
+}
+
+// Lists the JSON paths at which two normalized documents differ
+fn diff(path: &str, expected: &Value, actual: &Value, diffs: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                let key_path = format!("{}/{}", path, key);
+                match actual.get(key) {
+                    Some(actual_value) => diff(&key_path, expected_value, actual_value, diffs),
+                    None => diffs.push(format!("{}: missing, expected {}", key_path, expected_value)),
+                }
+            }
+            for (key, actual_value) in actual {
+                if !expected.contains_key(key) {
+                    diffs.push(format!("{}/{}: unexpected {}", path, key, actual_value));
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (i, (expected_value, actual_value)) in expected.iter().zip(actual).enumerate() {
+                diff(&format!("{}/{}", path, i), expected_value, actual_value, diffs);
+            }
+        }
+        _ if expected != actual => {
+            diffs.push(format!("{}: expected {}, got {}", path, expected, actual));
+        }
+        _ => {}
+    }
+}
+
+// Builds the document of a `<name>.bin` account fixture and diffs it against `<name>.json`
+fn check_fixture(json_path: &Path) -> Vec<String> {
+    let expected: Value = serde_json::from_str(&fs::read_to_string(json_path).unwrap()).unwrap();
+    let did: DidSol = expected["id"].as_str().unwrap().parse().unwrap();
+    let data = fs::read(json_path.with_extension("bin")).unwrap();
+    let did_account = DidAccount::try_deserialize(&mut data.as_slice()).unwrap();
+
+    let document = W3cDidDocument::from_did_account(&did, Some(&did_account));
+    let actual = serde_json::to_value(&document).unwrap();
+
+    let mut diffs = vec![];
+    diff("", &normalize(expected), &normalize(actual), &mut diffs);
+    diffs
+}
+
+#[test]
+fn test_documents_match_sol_did_client() {
+    let mut fixtures: Vec<_> = fs::read_dir("tests/conformance")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map_or(false, |extension| extension == "json"))
+        .collect();
+    fixtures.sort();
+    assert!(!fixtures.is_empty());
+
+    let failures: Vec<String> = fixtures
+        .iter()
+        .filter_map(|path| {
+            let diffs = check_fixture(path);
+            (!diffs.is_empty()).then(|| format!("{}:\n  {}", path.display(), diffs.join("\n  ")))
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "documents differ from the sol-did client:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn test_generative_document_matches_sol_did_client() {
+    let did: DidSol = "did:sol:devnet:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS".parse().unwrap();
+    let document = serde_json::to_value(W3cDidDocument::from_did_account(&did, None)).unwrap();
+
+    let default_key = format!("{}#default", did);
+    assert_eq!(document["@context"][1], "https://w3id.org/sol/v3.0");
+    assert_eq!(document["verificationMethod"][0]["id"], default_key);
+    assert_eq!(document["verificationMethod"][0]["publicKeyBase58"], did.pubkey().to_string());
+    assert_eq!(document["capabilityInvocation"], serde_json::json!([default_key]));
+    assert_eq!(document["authentication"], serde_json::json!([]));
+    assert_eq!(document["service"], serde_json::json!([]));
+}
+
+#[test]
+fn test_diff_reports_differences() {
+    let expected = normalize(serde_json::json!({ "id": "a", "keyAgreement": ["x", "y"], "service": [] }));
+    let actual = normalize(serde_json::json!({ "id": "a", "keyAgreement": ["y"], "controller": "b" }));
+
+    let mut diffs = vec![];
+    diff("", &expected, &actual, &mut diffs);
+    assert_eq!(diffs, vec![
+        r#"/keyAgreement: expected ["x","y"], got ["y"]"#.to_string(),
+        "/service: missing, expected []".to_string(),
+        r#"/controller: unexpected ["b"]"#.to_string()
+    ]);
+}
+
+#[test]
+fn test_didcomm_relationships_follow_flags() {
+    let resolver = SolResolver::default();
+    let expected: Value = serde_json::from_str(
+        &fs::read_to_string("tests/conformance/verification-methods.json").unwrap()
+    ).unwrap();
+    let did: DidSol = expected["id"].as_str().unwrap().parse().unwrap();
+
+    // re-bump the fixture so it passes account validation for its DID
+    let data = fs::read("tests/conformance/verification-methods.bin").unwrap();
+    let mut did_account = DidAccount::try_deserialize(&mut data.as_slice()).unwrap();
+    let (account_address, bump) = resolver.derive_did_account(&did);
+    did_account.bump = bump;
+    let mut data = vec![];
+    did_account.try_serialize(&mut data).unwrap();
+
+    let snapshot = DidAccountSnapshot {
+        did: did.to_string(),
+        did_pubkey: did.pubkey(),
+        account_address,
+        data: Some(data),
+        owner: Some(resolver.config().program_id()),
+        slot: 1,
+    };
+    let resolution = resolver.build_document(&snapshot).unwrap();
+
+    let ids = |values: &Value| -> Vec<String> {
+        values.as_array().unwrap().iter().map(|id| id.as_str().unwrap().to_string()).collect()
+    };
+    assert_eq!(resolution.did_doc.key_agreement, ids(&expected["keyAgreement"]));
+    assert_eq!(resolution.did_doc.authentication, ids(&expected["authentication"]));
+    assert_eq!(serde_json::to_value(&resolution.w3c_doc).unwrap()["keyAgreement"], expected["keyAgreement"]);
+}
@@ -0,0 +1,448 @@
+mod did_account_fixture;
+
+use async_trait::async_trait;
+use base64::{ engine::general_purpose::URL_SAFE_NO_PAD, Engine };
+use didcomm_soldid_resolver::canonical::canonicalize;
+use didcomm_soldid_resolver::credentials::RdfCanonicalizer;
+use didcomm_soldid_resolver::registrar::VM_FLAG_ASSERTION;
+use didcomm_soldid_resolver::{ CredentialVerifier, DidSol, SolResolverError };
+use did_account_fixture::{ did_account, fixture_resolver };
+use serde_json::{ json, Value };
+use sha2::{ Digest, Sha256 };
+use sol_did::state::VerificationMethod;
+use solana_sdk::signature::{ Keypair, Signature, Signer };
+
+const CONTEXT: [&str; 2] = ["https://www.w3.org/ns/credentials/v2", "https://www.w3.org/ns/credentials/examples/v2"];
+
+// An issuer whose `#default` key can only authenticate and whose `#assert` (Ed25519) and
+// `#secp` (secp256k1) keys issue credentials, and a holder with a `#default` key
+struct Fixture {
+    issuer: DidSol,
+    assertion_key: Keypair,
+    holder: DidSol,
+    holder_key: Keypair,
+    verifier: CredentialVerifier,
+}
+
+fn secp256k1_key() -> libsecp256k1::SecretKey {
+    libsecp256k1::SecretKey::parse(&[3; 32]).unwrap()
+}
+
+fn fixture() -> Fixture {
+    let issuer = DidSol::new("devnet", Keypair::new().pubkey());
+    let assertion_key = Keypair::new();
+    let holder_key = Keypair::new();
+    let holder = DidSol::new("devnet", holder_key.pubkey());
+
+    let issuer_account = did_account(
+        &issuer,
+        vec![
+            VerificationMethod {
+                fragment: "assert".to_string(),
+                flags: VM_FLAG_ASSERTION,
+                method_type: 0,
+                key_data: assertion_key.pubkey().to_bytes().to_vec(),
+            },
+            VerificationMethod {
+                fragment: "secp".to_string(),
+                flags: VM_FLAG_ASSERTION,
+                method_type: 2,
+                key_data: libsecp256k1::PublicKey
+                    ::from_secret_key(&secp256k1_key())
+                    .serialize_compressed()
+                    .to_vec(),
+            }
+        ],
+        vec![]
+    );
+    let holder_account = did_account(&holder, vec![], vec![]);
+
+    Fixture {
+        issuer,
+        assertion_key,
+        holder,
+        holder_key,
+        verifier: CredentialVerifier::new(fixture_resolver([issuer_account, holder_account])),
+    }
+}
+
+fn credential(issuer: &DidSol) -> Value {
+    json!({
+        "@context": CONTEXT,
+        "type": ["VerifiableCredential", "ExampleCredential"],
+        "issuer": issuer.to_string(),
+        "credentialSubject": { "id": "did:example:subject", "name": "Alice" }
+    })
+}
+
+fn hash_data(options: &str, document: &str) -> Vec<u8> {
+    let mut hash_data = Sha256::digest(options.as_bytes()).to_vec();
+    hash_data.extend(Sha256::digest(document.as_bytes()));
+    hash_data
+}
+
+// Proof options of `proof_type` under the document's context
+fn proof_options(document: &Value, proof_type: &str, kid: &str, purpose: &str) -> Value {
+    let mut options = json!({
+        "type": proof_type,
+        "created": "2026-01-01T00:00:00Z",
+        "verificationMethod": kid,
+        "proofPurpose": purpose
+    });
+    if proof_type == "DataIntegrityProof" {
+        options["cryptosuite"] = json!("eddsa-jcs-2022");
+    }
+    options["@context"] = document["@context"].clone();
+    options
+}
+
+// Secures `document` with an eddsa-jcs-2022 Data Integrity proof
+fn sign_jcs(document: &Value, key: &Keypair, kid: &str, purpose: &str) -> Value {
+    let options = proof_options(document, "DataIntegrityProof", kid, purpose);
+    let signature = key.sign_message(&hash_data(&canonicalize(&options), &canonicalize(document)));
+
+    let mut proof = options;
+    proof.as_object_mut().unwrap().remove("@context");
+    proof["proofValue"] = json!(format!("z{}", bs58::encode(signature.as_ref()).into_string()));
+
+    let mut secured = document.clone();
+    secured["proof"] = proof;
+    secured
+}
+
+fn jwt(alg: &str, kid: &str, claims: Value, sign: impl Fn(&[u8]) -> Vec<u8>) -> String {
+    let header = URL_SAFE_NO_PAD.encode(json!({ "alg": alg, "typ": "JWT", "kid": kid }).to_string());
+    let signing_input = format!("{}.{}", header, URL_SAFE_NO_PAD.encode(claims.to_string()));
+    let signature = sign(signing_input.as_bytes());
+    format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature))
+}
+
+fn sign_es256k(message: &[u8]) -> Vec<u8> {
+    let digest: [u8; 32] = Sha256::digest(message).into();
+    let (signature, _) = libsecp256k1::sign(&libsecp256k1::Message::parse(&digest), &secp256k1_key());
+    signature.serialize().to_vec()
+}
+
+// Stands in for a JSON-LD implementation; the suites only need a deterministic byte string
+struct JcsCanonicalizer;
+
+#[async_trait]
+impl RdfCanonicalizer for JcsCanonicalizer {
+    async fn canonicalize(&self, document: &Value) -> Result<String, SolResolverError> {
+        Ok(canonicalize(document))
+    }
+}
+
+#[tokio::test]
+async fn test_verifies_eddsa_jcs_2022_credential() {
+    let fixture = fixture();
+    let kid = fixture.issuer.to_did_url("assert");
+    let secured = sign_jcs(&credential(&fixture.issuer), &fixture.assertion_key, &kid, "assertionMethod");
+
+    let verified = fixture.verifier.verify_credential(&secured).await.unwrap();
+    assert_eq!(verified.verification_method.id, kid);
+    assert_eq!(verified.credential, secured);
+
+    let mut tampered = secured.clone();
+    tampered["credentialSubject"]["name"] = json!("Mallory");
+    let result = fixture.verifier.verify_credential(&tampered).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidSignature(_))));
+}
+
+#[tokio::test]
+async fn test_credentials_require_assertion_method() {
+    let fixture = fixture();
+    // a DID whose default key only authenticates
+    let issuer = &fixture.holder;
+
+    let secured = sign_jcs(
+        &credential(&issuer),
+        &fixture.holder_key,
+        &issuer.to_did_url("default"),
+        "assertionMethod"
+    );
+    let result = fixture.verifier.verify_credential(&secured).await;
+    assert!(
+        matches!(result, Err(SolResolverError::KeyNotAuthorized { relationship, .. }) if relationship == "assertionMethod")
+    );
+
+    // an authentication proof does not issue a credential
+    let secured = sign_jcs(&credential(&issuer), &fixture.holder_key, &issuer.to_did_url("default"), "authentication");
+    let result = fixture.verifier.verify_credential(&secured).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidCredential(_))));
+}
+
+#[tokio::test]
+async fn test_rejects_credential_of_another_issuer() {
+    let fixture = fixture();
+    let secured = sign_jcs(
+        &credential(&fixture.holder),
+        &fixture.assertion_key,
+        &fixture.issuer.to_did_url("assert"),
+        "assertionMethod"
+    );
+
+    let result = fixture.verifier.verify_credential(&secured).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidCredential(_))));
+}
+
+#[tokio::test]
+async fn test_rejects_credential_without_issuer() {
+    let fixture = fixture();
+    let mut document = credential(&fixture.issuer);
+    document.as_object_mut().unwrap().remove("issuer");
+    let secured = sign_jcs(&document, &fixture.assertion_key, &fixture.issuer.to_did_url("assert"), "assertionMethod");
+
+    let result = fixture.verifier.verify_credential(&secured).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidCredential(_))));
+}
+
+#[tokio::test]
+async fn test_rejects_expired_credential() {
+    let fixture = fixture();
+    let kid = fixture.issuer.to_did_url("assert");
+
+    let mut document = credential(&fixture.issuer);
+    document["validUntil"] = json!("2999-01-01T00:00:00Z");
+    let secured = sign_jcs(&document, &fixture.assertion_key, &kid, "assertionMethod");
+    fixture.verifier.verify_credential(&secured).await.unwrap();
+
+    for (name, date) in [
+        ("validUntil", "2020-01-01T00:00:00Z"),
+        ("expirationDate", "2020-01-01T00:00:00Z"),
+        ("validUntil", "next year"),
+    ] {
+        let mut document = credential(&fixture.issuer);
+        document[name] = json!(date);
+        let secured = sign_jcs(&document, &fixture.assertion_key, &kid, "assertionMethod");
+
+        let result = fixture.verifier.verify_credential(&secured).await;
+        assert!(matches!(result, Err(SolResolverError::InvalidCredential(_))), "{} {}", name, date);
+    }
+}
+
+// Example of the vc-di-eddsa specification's eddsa-jcs-2022 test vectors, signed by a
+// did:key method
+#[test]
+fn test_matches_eddsa_jcs_2022_test_vector() {
+    let secret_key = decode_multikey("z3u2en7t5LR2WtQH5PfFqMqwVHBeXouLzo6haApm8XHqvjxq", [0x80, 0x26]);
+    let public_key = decode_multikey("z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2", [0xed, 0x01]);
+    let keypair = Keypair::from_bytes(&[secret_key, public_key.clone()].concat()).unwrap();
+
+    let document =
+        json!({
+        "@context": CONTEXT,
+        "id": "urn:uuid:58172aac-d8ba-11ed-83dd-0b3aef56cc33",
+        "type": ["VerifiableCredential", "AlumniCredential"],
+        "name": "Alumni Credential",
+        "description": "A minimum viable example of an Alumni Credential.",
+        "issuer": "https://vc.example/issuers/5678",
+        "validFrom": "2023-01-01T00:00:00Z",
+        "credentialSubject": {
+            "id": "did:example:abcdefgh",
+            "alumniOf": "The School of Examples"
+        }
+    });
+    let options =
+        json!({
+        "type": "DataIntegrityProof",
+        "cryptosuite": "eddsa-jcs-2022",
+        "created": "2023-02-24T23:36:38Z",
+        "verificationMethod": "did:key:z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2#z6MkrJVnaZkeFzdQyMZu1cgjg7k1pZZ6pvBQ7XJPt4swbTQ2",
+        "proofPurpose": "assertionMethod",
+        "@context": CONTEXT
+    });
+
+    let hash_data = hash_data(&canonicalize(&options), &canonicalize(&document));
+    assert_eq!(
+        hash_data.iter().map(|byte| format!("{:02x}", byte)).collect::<String>(),
+        "66ab154f5c2890a140cb8388a22a160454f80575f6eae09e5a097cabe539a1db\
+         59b7cb6251b8991add1ce0bc83107e3db9dbbab5bd2c28f687db1a03abc92f19"
+    );
+
+    let proof_value = "z2HnFSSPPBzR36zdDgK8PbEHeXbR56YF24jwMpt3R1eHXQzJDMWS93FCzpvJpwTWd3GAVFuUfjoJdcnTMuVor51aX";
+    let signature = Signature::try_from(bs58::decode(&proof_value[1..]).into_vec().unwrap()).unwrap();
+    assert!(signature.verify(&public_key, &hash_data));
+    assert_eq!(keypair.sign_message(&hash_data), signature);
+}
+
+fn decode_multikey(multibase: &str, multicodec: [u8; 2]) -> Vec<u8> {
+    let bytes = bs58::decode(&multibase[1..]).into_vec().unwrap();
+    assert_eq!(bytes[..2], multicodec);
+    bytes[2..].to_vec()
+}
+
+#[tokio::test]
+async fn test_verifies_rdf_suites_with_canonicalizer() {
+    let fixture = fixture();
+    let kid = fixture.issuer.to_did_url("assert");
+    let document = credential(&fixture.issuer);
+    let verifier = fixture.verifier.clone().with_rdf_canonicalizer(JcsCanonicalizer);
+
+    // Ed25519Signature2020: multibase proofValue
+    let options = proof_options(&document, "Ed25519Signature2020", &kid, "assertionMethod");
+    let signature = fixture.assertion_key.sign_message(
+        &hash_data(&canonicalize(&options), &canonicalize(&document))
+    );
+    let mut secured = document.clone();
+    secured["proof"] = json!({
+        "type": "Ed25519Signature2020",
+        "created": "2026-01-01T00:00:00Z",
+        "verificationMethod": kid,
+        "proofPurpose": "assertionMethod",
+        "proofValue": format!("z{}", bs58::encode(signature.as_ref()).into_string())
+    });
+
+    let result = fixture.verifier.verify_credential(&secured).await;
+    assert!(matches!(result, Err(SolResolverError::UnsupportedProof(_))));
+    verifier.verify_credential(&secured).await.unwrap();
+
+    // Ed25519Signature2018: detached JWS with an unencoded payload
+    let options = proof_options(&document, "Ed25519Signature2018", &kid, "assertionMethod");
+    let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"EdDSA","b64":false,"crit":["b64"]}"#);
+    let mut signing_input = format!("{}.", header).into_bytes();
+    signing_input.extend(hash_data(&canonicalize(&options), &canonicalize(&document)));
+    let signature = fixture.assertion_key.sign_message(&signing_input);
+    let mut secured = document.clone();
+    secured["proof"] = json!({
+        "type": "Ed25519Signature2018",
+        "created": "2026-01-01T00:00:00Z",
+        "verificationMethod": kid,
+        "proofPurpose": "assertionMethod",
+        "jws": format!("{}..{}", header, URL_SAFE_NO_PAD.encode(signature.as_ref()))
+    });
+
+    verifier.verify_credential(&secured).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_verifies_jwt_credential() {
+    let fixture = fixture();
+    let claims = json!({
+        "iss": fixture.issuer.to_string(),
+        "sub": "did:example:subject",
+        "vc": credential(&fixture.issuer)
+    });
+
+    let token = jwt("EdDSA", &fixture.issuer.to_did_url("assert"), claims.clone(), |input| {
+        fixture.assertion_key.sign_message(input).as_ref().to_vec()
+    });
+    let verified = fixture.verifier.verify_jwt_credential(&token).await.unwrap();
+    assert_eq!(verified.credential, credential(&fixture.issuer));
+
+    // relative kid of the issuer
+    let token = jwt("ES256K", "#secp", claims.clone(), sign_es256k);
+    let verified = fixture.verifier.verify_jwt_credential(&token).await.unwrap();
+    assert_eq!(verified.verification_method.id, fixture.issuer.to_did_url("secp"));
+
+    // NumericDates may be fractional
+    for (name, date) in [("exp", json!(1_000_000_000)), ("exp", json!(1_000_000_000.5)), ("nbf", json!(32_503_680_000.5))] {
+        let mut invalid = claims.clone();
+        invalid[name] = date;
+        let token = jwt("ES256K", "#secp", invalid, sign_es256k);
+        let result = fixture.verifier.verify_jwt_credential(&token).await;
+        assert!(matches!(result, Err(SolResolverError::InvalidCredential(_))));
+    }
+
+    // JWTs are never unencoded-payload JWSs
+    for header in [
+        json!({ "alg": "EdDSA", "kid": "#assert", "b64": false, "crit": ["b64"] }),
+        json!({ "alg": "EdDSA", "kid": "#assert", "crit": ["b64"] }),
+    ] {
+        let header = URL_SAFE_NO_PAD.encode(header.to_string());
+        let signing_input = format!("{}.{}", header, URL_SAFE_NO_PAD.encode(claims.to_string()));
+        let signature = fixture.assertion_key.sign_message(signing_input.as_bytes());
+        let token = format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature.as_ref()));
+        let result = fixture.verifier.verify_jwt_credential(&token).await;
+        assert!(matches!(result, Err(SolResolverError::InvalidCredential(_))));
+    }
+
+    // the credential's own expiry applies too
+    let mut expired = claims;
+    expired["vc"]["validUntil"] = json!("2020-01-01T00:00:00Z");
+    let token = jwt("ES256K", "#secp", expired, sign_es256k);
+    let result = fixture.verifier.verify_jwt_credential(&token).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidCredential(_))));
+}
+
+#[tokio::test]
+async fn test_verifies_presentation_and_its_credentials() {
+    let fixture = fixture();
+    let embedded = sign_jcs(
+        &credential(&fixture.issuer),
+        &fixture.assertion_key,
+        &fixture.issuer.to_did_url("assert"),
+        "assertionMethod"
+    );
+    let token = jwt(
+        "ES256K",
+        "#secp",
+        json!({ "iss": fixture.issuer.to_string(), "vc": credential(&fixture.issuer) }),
+        sign_es256k
+    );
+
+    let presentation =
+        json!({
+        "@context": CONTEXT,
+        "type": ["VerifiablePresentation"],
+        "holder": fixture.holder.to_string(),
+        "verifiableCredential": [embedded, token]
+    });
+    let secured = sign_jcs(
+        &presentation,
+        &fixture.holder_key,
+        &fixture.holder.to_did_url("default"),
+        "authentication"
+    );
+
+    let verified = fixture.verifier.verify_presentation(&secured).await.unwrap();
+    assert_eq!(verified.verification_method.id, fixture.holder.to_did_url("default"));
+    assert_eq!(verified.credentials.len(), 2);
+
+    // assertion keys do not authenticate
+    let mut presentation = presentation;
+    presentation["holder"] = json!(fixture.issuer.to_string());
+    let secured = sign_jcs(
+        &presentation,
+        &fixture.assertion_key,
+        &fixture.issuer.to_did_url("assert"),
+        "authentication"
+    );
+    let result = fixture.verifier.verify_presentation(&secured).await;
+    assert!(
+        matches!(result, Err(SolResolverError::KeyNotAuthorized { relationship, .. }) if relationship == "authentication")
+    );
+}
+
+#[tokio::test]
+async fn test_verifies_jwt_presentation() {
+    let fixture = fixture();
+    let credential_token = jwt(
+        "EdDSA",
+        &fixture.issuer.to_did_url("assert"),
+        json!({ "iss": fixture.issuer.to_string(), "vc": credential(&fixture.issuer) }),
+        |input| fixture.assertion_key.sign_message(input).as_ref().to_vec()
+    );
+    let claims = json!({
+        "iss": fixture.holder.to_string(),
+        "vp": {
+            "@context": CONTEXT,
+            "type": ["VerifiablePresentation"],
+            "verifiableCredential": [credential_token]
+        }
+    });
+
+    let token = jwt("EdDSA", "#default", claims.clone(), |input| {
+        fixture.holder_key.sign_message(input).as_ref().to_vec()
+    });
+    let verified = fixture.verifier.verify_jwt_presentation(&token).await.unwrap();
+    assert_eq!(verified.credentials.len(), 1);
+    assert_eq!(verified.credentials[0].verification_method.id, fixture.issuer.to_did_url("assert"));
+
+    // signed by a key of another DID
+    let token = jwt("EdDSA", &fixture.issuer.to_did_url("default"), claims, |input| {
+        fixture.holder_key.sign_message(input).as_ref().to_vec()
+    });
+    let result = fixture.verifier.verify_jwt_presentation(&token).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,63 @@
+mod mock_rpc;
+
+use std::str::FromStr;
+use didcomm_soldid_resolver::{ DidSol, NetworkConfig, SolResolver, SolResolverConfig, SolResolverError };
+use didcomm_soldid_resolver::did_sol::DidSolParseError;
+use didcomm_soldid_resolver::resolution::{ ResolutionError, ResolutionResult };
+use mock_rpc::{ missing_account, start_mock_rpc };
+use solana_sdk::pubkey::Pubkey;
+
+const KEY: &str = "BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+const PRIVATE_PROGRAM_ID: &str = "6wciiqLfbXB1WocZsDBXE2xMLJNNFxnyL6Mvo91XjSkR";
+
+fn private_did_account() -> Pubkey {
+    let program_id = Pubkey::from_str(PRIVATE_PROGRAM_ID).unwrap();
+    let did_pubkey = Pubkey::from_str(KEY).unwrap();
+    Pubkey::find_program_address(&[b"did-account", did_pubkey.as_ref()], &program_id).0
+}
+
+#[tokio::test]
+async fn test_resolves_did_on_private_cluster() {
+    // only answers for the DID account derived with the private cluster's program ID
+    let rpc_url = start_mock_rpc(|_, params| {
+        if params[0].as_str() == Some(private_did_account().to_string().as_str()) {
+            Ok(missing_account(42))
+        } else {
+            Err(400)
+        }
+    }).await;
+
+    let network = NetworkConfig::new(&rpc_url).with_program_id(
+        Pubkey::from_str(PRIVATE_PROGRAM_ID).unwrap()
+    );
+    let resolver = SolResolver::new(SolResolverConfig::default().with_network("acme", network));
+
+    let did = format!("did:sol:acme:{}", KEY);
+    let resolution = resolver.resolve_document(&did).await.unwrap();
+    assert_eq!(resolution.did_doc.id, did);
+    assert_eq!(resolution.metadata.slot, Some(42));
+
+    let (address, _) = resolver.derive_did_account(&did.parse::<DidSol>().unwrap());
+    assert_eq!(address, private_did_account());
+    // other networks keep the default program ID
+    let (devnet_address, _) = resolver.derive_did_account(
+        &DidSol::new("devnet", Pubkey::from_str(KEY).unwrap())
+    );
+    assert_ne!(devnet_address, address);
+}
+
+#[tokio::test]
+async fn test_unknown_network_is_method_not_supported() {
+    let resolver = SolResolver::default();
+    let did = format!("did:sol:acme:{}", KEY);
+
+    let result = resolver.resolve_document(&did).await;
+    assert!(matches!(&result, Err(SolResolverError::UnsupportedNetwork(network)) if network == "acme"));
+
+    let resolution = ResolutionResult::from_resolution(result, "application/did+ld+json");
+    assert_eq!(resolution.did_resolution_metadata.error, Some(ResolutionError::MethodNotSupported));
+
+    // malformed network names are still invalid DIDs
+    let result = resolver.resolve_document(&format!("did:sol:AC ME:{}", KEY)).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidDid(DidSolParseError::InvalidNetwork(_)))));
+}
@@ -0,0 +1,64 @@
+use anchor_client::anchor_lang::AccountSerialize;
+use didcomm_soldid_resolver::registrar::{
+    VM_FLAG_AUTHENTICATION,
+    VM_FLAG_CAPABILITY_INVOCATION,
+    VM_FLAG_OWNERSHIP_PROOF,
+};
+use didcomm_soldid_resolver::{ DidSol, FixtureAccountSource, SolResolver };
+use sol_did::state::{ DidAccount, Service, VerificationMethod };
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+/// Flags of the `#default` key of fixture DID accounts: it authenticates, invokes
+/// capabilities and proves ownership of the DID.
+#[allow(dead_code)]
+pub(crate) const DEFAULT_KEY_FLAGS: u16 =
+    VM_FLAG_AUTHENTICATION | VM_FLAG_CAPABILITY_INVOCATION | VM_FLAG_OWNERSHIP_PROOF;
+
+/// DID account of `did` at its derived address, owned by the default sol-did program: the
+/// DID key as `#default` with `DEFAULT_KEY_FLAGS`, followed by `verification_methods` and
+/// `services`.
+#[allow(dead_code)]
+pub(crate) fn did_account(
+    did: &DidSol,
+    verification_methods: Vec<VerificationMethod>,
+    services: Vec<Service>
+) -> (Pubkey, Account) {
+    let plain = SolResolver::default();
+    let (address, bump) = plain.derive_did_account(did);
+
+    let did_account = DidAccount {
+        version: 0,
+        bump,
+        nonce: 0,
+        initial_verification_method: VerificationMethod {
+            fragment: "default".to_string(),
+            flags: DEFAULT_KEY_FLAGS,
+            method_type: 0,
+            key_data: did.pubkey().to_bytes().to_vec(),
+        },
+        verification_methods,
+        services,
+        native_controllers: vec![],
+        other_controllers: vec![],
+    };
+    let mut data = vec![];
+    did_account.try_serialize(&mut data).unwrap();
+
+    (address, Account {
+        lamports: 1_461_600,
+        data,
+        owner: plain.config().program_id(),
+        executable: false,
+        rent_epoch: 0,
+    })
+}
+
+/// Default resolver reading `accounts` instead of RPC.
+#[allow(dead_code)]
+pub(crate) fn fixture_resolver(accounts: impl IntoIterator<Item = (Pubkey, Account)>) -> SolResolver {
+    let source = accounts
+        .into_iter()
+        .fold(FixtureAccountSource::new(), |source, (address, account)| source.with_account(address, account));
+    SolResolver::default().with_account_source(source)
+}
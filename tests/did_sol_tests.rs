@@ -0,0 +1,75 @@
+use std::str::FromStr;
+use didcomm_soldid_resolver::{ DidSol, SolResolverError };
+use didcomm_soldid_resolver::did_sol::DidSolParseError;
+use solana_sdk::pubkey::Pubkey;
+
+const KEY: &str = "BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+
+#[test]
+fn test_parse_and_display() {
+    let did = DidSol::from_str(&format!("did:sol:devnet:{}", KEY)).unwrap();
+    assert_eq!(did.network(), "devnet");
+    assert_eq!(did.pubkey(), Pubkey::from_str(KEY).unwrap());
+    assert_eq!(did.to_string(), format!("did:sol:devnet:{}", KEY));
+    assert_eq!(did.to_did_url("default"), format!("did:sol:devnet:{}#default", KEY));
+
+    let mainnet: DidSol = format!("did:sol:{}", KEY).parse().unwrap();
+    assert_eq!(mainnet.network(), "mainnet");
+    assert_eq!(mainnet, DidSol::mainnet(Pubkey::from_str(KEY).unwrap()));
+    assert_eq!(mainnet.to_string(), format!("did:sol:{}", KEY));
+
+    // an explicit mainnet segment is normalized away
+    let explicit: DidSol = format!("did:sol:mainnet:{}", KEY).parse().unwrap();
+    assert_eq!(explicit, mainnet);
+}
+
+#[test]
+fn test_custom_network_names() {
+    let did: DidSol = format!("did:sol:acme-permissioned_1:{}", KEY).parse().unwrap();
+    assert_eq!(did.network(), "acme-permissioned_1");
+    assert_eq!(did.to_string(), format!("did:sol:acme-permissioned_1:{}", KEY));
+}
+
+#[test]
+fn test_build_from_pubkey() {
+    let pubkey = Pubkey::new_unique();
+    let did = DidSol::new("localnet", pubkey);
+    assert_eq!(did.to_string(), format!("did:sol:localnet:{}", pubkey));
+    assert_eq!(did.to_string().parse::<DidSol>().unwrap(), did);
+}
+
+#[test]
+fn test_parse_errors() {
+    let parse = |did: &str| DidSol::from_str(did).unwrap_err();
+
+    assert_eq!(parse(&format!("did:web:{}", KEY)), DidSolParseError::NotDidSol);
+    assert_eq!(parse("did:sol:"), DidSolParseError::InvalidFormat);
+    assert_eq!(parse(&format!("did:sol:devnet:extra:{}", KEY)), DidSolParseError::InvalidFormat);
+    assert_eq!(
+        parse(&format!("did:sol:Moon.Net:{}", KEY)),
+        DidSolParseError::InvalidNetwork("Moon.Net".to_string())
+    );
+    assert_eq!(parse("did:sol:not-a-key"), DidSolParseError::InvalidBase58("not-a-key".to_string()));
+    // valid base58 of the right length range, but not 32 bytes
+    assert_eq!(parse("did:sol:11111111111111111111111111111111111111111"), DidSolParseError::InvalidKeyLength(41));
+    assert_eq!(parse("did:sol:2NEpo7TZRRrLZSi2U"), DidSolParseError::InvalidKeyLength(12));
+}
+
+#[test]
+fn test_parse_errors_map_to_resolver_errors() {
+    let err: SolResolverError = DidSolParseError::InvalidKeyLength(11).into();
+    assert!(matches!(err, SolResolverError::InvalidDid(DidSolParseError::InvalidKeyLength(11))));
+    let err: SolResolverError = DidSolParseError::NotDidSol.into();
+    assert!(matches!(err, SolResolverError::InvalidDid(DidSolParseError::NotDidSol)));
+    assert_eq!(err.to_string(), "Invalid DID format: DID does not start with did:sol:");
+}
+
+#[test]
+fn test_serde_round_trip() {
+    let did: DidSol = format!("did:sol:testnet:{}", KEY).parse().unwrap();
+    let json = serde_json::to_string(&did).unwrap();
+    assert_eq!(json, format!("\"did:sol:testnet:{}\"", KEY));
+    assert_eq!(serde_json::from_str::<DidSol>(&json).unwrap(), did);
+
+    assert!(serde_json::from_str::<DidSol>("\"did:sol:not-a-key\"").is_err());
+}
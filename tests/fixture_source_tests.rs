@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use anchor_client::anchor_lang::AccountSerialize;
+use base64::{ engine::general_purpose::STANDARD, Engine };
+use didcomm::did::DIDResolver;
+use didcomm_soldid_resolver::{ DidSol, FixtureAccountSource, SolResolver, SolResolverError };
+use serde_json::json;
+use sol_did::state::{ DidAccount, Service, VerificationMethod };
+use solana_sdk::pubkey::Pubkey;
+
+const KEY: &str = "BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+const DID: &str = "did:sol:devnet:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+
+// `solana account <PDA> --output json` of a DID account with one service
+fn dump(resolver: &SolResolver) -> (Pubkey, String) {
+    let did_pubkey = Pubkey::from_str(KEY).unwrap();
+    let (address, bump) = resolver.derive_did_account(&DidSol::new("devnet", did_pubkey));
+
+    let account = DidAccount {
+        version: 0,
+        bump,
+        nonce: 0,
+        initial_verification_method: VerificationMethod {
+            fragment: "default".to_string(),
+            flags: 1 << 6,
+            method_type: 0,
+            key_data: did_pubkey.to_bytes().to_vec(),
+        },
+        verification_methods: vec![],
+        services: vec![Service {
+            fragment: "agent".to_string(),
+            service_type: "TestService".to_string(),
+            service_endpoint: "https://test-service.com".to_string(),
+        }],
+        native_controllers: vec![],
+        other_controllers: vec![],
+    };
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+
+    let dump =
+        json!({
+        "pubkey": address.to_string(),
+        "account": {
+            "lamports": 1_461_600,
+            "data": [STANDARD.encode(&data), "base64"],
+            "owner": resolver.config().program_id().to_string(),
+            "executable": false,
+            "rentEpoch": 361,
+            "space": data.len()
+        }
+    });
+
+    (address, dump.to_string())
+}
+
+fn dump_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("soldid-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_resolves_from_dump() {
+    let (_, json) = dump(&SolResolver::default());
+    let source = FixtureAccountSource::new().with_dump(&json).unwrap().with_slot(250_000_000);
+    let resolver = SolResolver::default().with_account_source(source);
+
+    let resolution = resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(resolution.did_doc.id, DID);
+    assert_eq!(resolution.did_doc.service.len(), 1);
+    assert_eq!(resolution.did_doc.service[0].id, format!("{}#agent", DID));
+    assert_eq!(resolution.metadata.slot, Some(250_000_000));
+}
+
+#[tokio::test]
+async fn test_loads_directory_keyed_by_pda() {
+    let (address, json) = dump(&SolResolver::default());
+    let dir = dump_dir("dumps");
+    std::fs::write(dir.join(format!("{}.json", address)), &json).unwrap();
+    std::fs::write(dir.join("notes.txt"), "not a dump").unwrap();
+
+    let source = FixtureAccountSource::load(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(source.addresses().collect::<Vec<_>>(), vec![&address]);
+
+    let resolver = SolResolver::default().with_account_source(source);
+    let doc = resolver.resolve(DID).await.unwrap().unwrap();
+    assert_eq!(doc.service.len(), 1);
+
+    // DIDs without a dump resolve like chainless DIDs
+    let other = DidSol::new("devnet", Pubkey::new_unique()).to_string();
+    let doc = resolver.resolve(&other).await.unwrap().unwrap();
+    assert!(doc.service.is_empty());
+}
+
+#[test]
+fn test_rejects_dump_of_another_address() {
+    let (_, json) = dump(&SolResolver::default());
+    let dir = dump_dir("mismatch");
+    let path = dir.join(format!("{}.json", Pubkey::new_unique()));
+    std::fs::write(&path, &json).unwrap();
+
+    let result = FixtureAccountSource::from_file(&path);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(result, Err(SolResolverError::InvalidAccountDump(_))));
+}
+
+#[test]
+fn test_rejects_malformed_dump() {
+    let result = FixtureAccountSource::new().with_dump(r#"{ "pubkey": "not-a-key", "account": {} }"#);
+    assert!(matches!(result, Err(SolResolverError::InvalidAccountDump(_))));
+}
@@ -0,0 +1,241 @@
+use std::str::FromStr;
+use anchor_client::anchor_lang::{ AccountSerialize, AnchorSerialize };
+use didcomm_soldid_resolver::resolution::DidAccountVersion;
+use didcomm_soldid_resolver::{ DidSol, FixtureAccountSource, SolResolver, SolResolverConfig, SolResolverError };
+use sol_did::state::{ DidAccount, VerificationMethod };
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+const KEY: &str = "BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+const DID: &str = "did:sol:devnet:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+const LEGACY_PROGRAM_ID: &str = "idDa4XeCjVwKcprVAo812coUQbovSZ4kDGJf2sPaBnM";
+
+// Size legacy SolData accounts were allocated with; the document is zero padded
+const LEGACY_ACCOUNT_SIZE: usize = 1_000;
+
+fn legacy_program_id() -> Pubkey {
+    Pubkey::from_str(LEGACY_PROGRAM_ID).unwrap()
+}
+
+fn legacy_address(authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[authority.as_ref(), b"solid"], &legacy_program_id()).0
+}
+
+fn second_key() -> Pubkey {
+    Pubkey::new_from_array([7; 32])
+}
+
+fn controller_key() -> Pubkey {
+    Pubkey::new_from_array([9; 32])
+}
+
+fn write_strings(data: &mut Vec<u8>, strings: &[&str]) {
+    (strings.len() as u32).serialize(data).unwrap();
+    for string in strings {
+        string.to_string().serialize(data).unwrap();
+    }
+}
+
+// Borsh encoded SolData of the pre-Anchor program: a default key that can authenticate,
+// a second key for key agreement, a devnet controller and one service
+fn legacy_data(authority: &Pubkey) -> Vec<u8> {
+    legacy_data_with_controller(authority, 2)
+}
+
+// As `legacy_data`, with the controller on the cluster of type `cluster`
+fn legacy_data_with_controller(authority: &Pubkey, cluster: u8) -> Vec<u8> {
+    let mut data = vec![];
+    authority.serialize(&mut data).unwrap();
+    "0".to_string().serialize(&mut data).unwrap();
+
+    // controller: cluster type, authority, url field
+    (1u32).serialize(&mut data).unwrap();
+    cluster.serialize(&mut data).unwrap();
+    controller_key().serialize(&mut data).unwrap();
+    String::new().serialize(&mut data).unwrap();
+
+    // verification methods: id, type, public key
+    (2u32).serialize(&mut data).unwrap();
+    for (id, key) in [("default", *authority), ("key2", second_key())] {
+        id.to_string().serialize(&mut data).unwrap();
+        "Ed25519VerificationKey2018".to_string().serialize(&mut data).unwrap();
+        key.serialize(&mut data).unwrap();
+    }
+
+    write_strings(&mut data, &["default"]); // authentication
+    write_strings(&mut data, &["default"]); // capability invocation
+    write_strings(&mut data, &[]); // capability delegation
+    write_strings(&mut data, &["#key2"]); // key agreement
+    write_strings(&mut data, &[]); // assertion method
+
+    // services: id, type, endpoint, description
+    (1u32).serialize(&mut data).unwrap();
+    for field in ["agent", "DIDCommMessaging", "https://agent.example.com", "Agent"] {
+        field.to_string().serialize(&mut data).unwrap();
+    }
+
+    data.resize(LEGACY_ACCOUNT_SIZE, 0);
+    data
+}
+
+fn legacy_account(data: Vec<u8>) -> Account {
+    Account {
+        lamports: 7_850_880,
+        data,
+        owner: legacy_program_id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn resolver(source: FixtureAccountSource, resolve_legacy: bool) -> SolResolver {
+    let config = SolResolverConfig::default().with_resolve_legacy(resolve_legacy);
+    SolResolver::new(config).with_account_source(source)
+}
+
+#[tokio::test]
+async fn test_resolves_legacy_account() {
+    let authority = Pubkey::from_str(KEY).unwrap();
+    let source = FixtureAccountSource::new().with_account(
+        legacy_address(&authority),
+        legacy_account(legacy_data(&authority))
+    );
+
+    let resolution = resolver(source, true).resolve_document(DID).await.unwrap();
+
+    assert_eq!(
+        resolution.metadata.account_version,
+        Some(DidAccountVersion::Legacy { version: "0".to_string() })
+    );
+    assert!(!resolution.metadata.deactivated);
+
+    let did_doc = resolution.did_doc;
+    assert_eq!(did_doc.verification_method.len(), 2);
+    assert_eq!(did_doc.authentication, vec![format!("{}#default", DID)]);
+    assert_eq!(did_doc.key_agreement, vec![format!("{}#key2", DID)]);
+    assert_eq!(did_doc.service.len(), 1);
+    assert_eq!(did_doc.service[0].id, format!("{}#agent", DID));
+
+    let w3c_doc = resolution.w3c_doc;
+    assert_eq!(w3c_doc.controller, vec![DidSol::new("devnet", controller_key()).to_string()]);
+    assert_eq!(w3c_doc.capability_invocation, vec![format!("{}#default", DID)]);
+}
+
+#[tokio::test]
+async fn test_legacy_fallback_is_opt_in() {
+    let authority = Pubkey::from_str(KEY).unwrap();
+    let source = FixtureAccountSource::new().with_account(
+        legacy_address(&authority),
+        legacy_account(legacy_data(&authority))
+    );
+
+    let resolution = resolver(source, false).resolve_document(DID).await.unwrap();
+
+    // generative document
+    assert_eq!(resolution.metadata.account_version, None);
+    assert_eq!(resolution.did_doc.verification_method.len(), 1);
+    assert!(resolution.did_doc.service.is_empty());
+}
+
+#[tokio::test]
+async fn test_did_account_takes_precedence_over_legacy_account() {
+    let authority = Pubkey::from_str(KEY).unwrap();
+    let plain = SolResolver::default();
+    let (address, bump) = plain.derive_did_account(&DidSol::new("devnet", authority));
+
+    let did_account = DidAccount {
+        version: 0,
+        bump,
+        nonce: 0,
+        initial_verification_method: VerificationMethod {
+            fragment: "default".to_string(),
+            flags: (1 << 3) | (1 << 6),
+            method_type: 0,
+            key_data: authority.to_bytes().to_vec(),
+        },
+        verification_methods: vec![],
+        services: vec![],
+        native_controllers: vec![],
+        other_controllers: vec![],
+    };
+    let mut data = vec![];
+    did_account.try_serialize(&mut data).unwrap();
+
+    let source = FixtureAccountSource::new()
+        .with_account(address, Account {
+            lamports: 1_461_600,
+            data,
+            owner: plain.config().program_id(),
+            executable: false,
+            rent_epoch: 0,
+        })
+        .with_account(legacy_address(&authority), legacy_account(legacy_data(&authority)));
+
+    let resolver = resolver(source, true);
+    let resolution = resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(resolution.metadata.account_version, Some(DidAccountVersion::DidAccount { version: 0 }));
+    assert!(resolution.did_doc.service.is_empty());
+
+    let snapshot = resolver.fetch_account(DID).await.unwrap();
+    assert_eq!(snapshot.account_address, address);
+}
+
+#[tokio::test]
+async fn test_rejects_legacy_account_of_another_authority() {
+    let authority = Pubkey::from_str(KEY).unwrap();
+    let source = FixtureAccountSource::new().with_account(
+        legacy_address(&authority),
+        legacy_account(legacy_data(&second_key()))
+    );
+
+    let result = resolver(source, true).resolve_document(DID).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidLegacyAccount(_))));
+}
+
+#[tokio::test]
+async fn test_rejects_truncated_legacy_account() {
+    let authority = Pubkey::from_str(KEY).unwrap();
+    let mut data = legacy_data(&authority);
+    data.truncate(100);
+    let source = FixtureAccountSource::new().with_account(
+        legacy_address(&authority),
+        legacy_account(data)
+    );
+
+    let result = resolver(source, true).resolve_document(DID).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidLegacyAccount(_))));
+}
+
+#[tokio::test]
+async fn test_legacy_controllers_keep_their_cluster() {
+    let authority = Pubkey::from_str(KEY).unwrap();
+
+    for (cluster, controller) in [
+        (1, DidSol::mainnet(controller_key())),
+        (0, DidSol::new("testnet", controller_key())),
+    ] {
+        let source = FixtureAccountSource::new().with_account(
+            legacy_address(&authority),
+            legacy_account(legacy_data_with_controller(&authority, cluster))
+        );
+
+        let resolution = resolver(source, true).resolve_document(DID).await.unwrap();
+        assert_eq!(resolution.w3c_doc.controller, vec![controller.to_string()]);
+    }
+}
+
+#[tokio::test]
+async fn test_rejects_legacy_account_outside_legacy_address() {
+    let authority = Pubkey::from_str(KEY).unwrap();
+    let plain = SolResolver::default();
+    let (did_account_address, _) = plain.derive_did_account(&DID.parse::<DidSol>().unwrap());
+
+    // a SolData account owned by the legacy program, but at the DID account address
+    let source = FixtureAccountSource::new().with_account(
+        did_account_address,
+        legacy_account(legacy_data(&authority))
+    );
+
+    let result = resolver(source, true).resolve_document(DID).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidLegacyAccount(_))));
+}
@@ -0,0 +1,91 @@
+use serde_json::{ json, Value };
+use tokio::io::{ AsyncReadExt, AsyncWriteExt };
+use tokio::net::{ TcpListener, TcpStream };
+
+/// Minimal JSON-RPC server standing in for a Solana RPC node.
+///
+/// `handler` receives the JSON-RPC method and params and returns either the `result`
+/// value, or an HTTP status code to fail the request with.
+pub(crate) async fn start_mock_rpc(handler: fn(&str, &Value) -> Result<Value, u16>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            tokio::spawn(handle_connection(socket, handler));
+        }
+    });
+
+    format!("http://{}", addr)
+}
+
+async fn handle_connection(mut socket: TcpStream, handler: fn(&str, &Value) -> Result<Value, u16>) {
+    let mut request = vec![];
+    let mut buf = [0u8; 4096];
+
+    // read headers, then the body announced by Content-Length
+    let body_start = loop {
+        let n = match socket.read(&mut buf).await {
+            Ok(0) | Err(_) => {
+                return;
+            }
+            Ok(n) => n,
+        };
+        request.extend_from_slice(&buf[..n]);
+        if let Some(pos) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+    let headers = String::from_utf8_lossy(&request[..body_start]).to_lowercase();
+    let content_length = headers
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .and_then(|len| len.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while request.len() < body_start + content_length {
+        match socket.read(&mut buf).await {
+            Ok(0) | Err(_) => {
+                return;
+            }
+            Ok(n) => request.extend_from_slice(&buf[..n]),
+        }
+    }
+
+    let body: Value = serde_json::from_slice(&request[body_start..]).unwrap_or(Value::Null);
+    let method = body["method"].as_str().unwrap_or_default();
+
+    let (status, payload) = match handler(method, &body["params"]) {
+        Ok(result) => ("200 OK".to_string(), json!({ "jsonrpc": "2.0", "result": result, "id": body["id"] })),
+        Err(code) => (format!("{} Mock Failure", code), json!({ "error": "mock failure" })),
+    };
+    let payload = payload.to_string();
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        payload.len(),
+        payload
+    );
+    let _ = socket.write_all(response.as_bytes()).await;
+}
+
+/// `getAccountInfo` result for an account that does not exist.
+#[allow(dead_code)]
+pub(crate) fn missing_account(slot: u64) -> Value {
+    json!({ "context": { "slot": slot }, "value": null })
+}
+
+/// `getAccountInfo` result for an existing account with base64 encoded `data`.
+#[allow(dead_code)]
+pub(crate) fn account(slot: u64, owner: &str, data: &str) -> Value {
+    json!({
+        "context": { "slot": slot },
+        "value": {
+            "data": [data, "base64"],
+            "executable": false,
+            "lamports": 1_000_000,
+            "owner": owner,
+            "rentEpoch": 0
+        }
+    })
+}
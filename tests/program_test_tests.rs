@@ -0,0 +1,140 @@
+#![cfg(feature = "test-utils")]
+
+use didcomm::did::{ DIDResolver, ServiceKind, VerificationMaterial, VerificationMethodType };
+use didcomm_soldid_resolver::{ ResolveOptions, SolResolverError };
+use didcomm_soldid_resolver::registrar::{ VM_FLAG_ASSERTION, VM_FLAG_KEY_AGREEMENT };
+use didcomm_soldid_resolver::test_utils::DidTestBank;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{ Keypair, Signer };
+
+#[tokio::test]
+async fn test_resolves_did_created_on_bank() {
+    let mut bank = DidTestBank::start().await;
+    let authority = Keypair::new();
+    let did = bank.create_did(&authority).await.unwrap();
+
+    let resolver = bank.resolver();
+    let resolution = resolver.resolve_document(&did.to_string()).await.unwrap();
+    let doc = resolution.did_doc;
+    assert_eq!(doc.id, did.to_string());
+    assert_eq!(doc.verification_method.len(), 1);
+    assert_eq!(doc.verification_method[0].id, did.to_did_url("default"));
+    assert!(
+        matches!(
+            &doc.verification_method[0].verification_material,
+            VerificationMaterial::Base58 { public_key_base58 } if *public_key_base58 == authority.pubkey().to_string()
+        )
+    );
+    assert!(doc.service.is_empty());
+
+    // the account was read from the bank, not derived generatively
+    let snapshot = resolver.fetch_account(&did.to_string()).await.unwrap();
+    assert_eq!(snapshot.account_address, bank.registrar(&authority.pubkey()).did_account());
+    assert!(snapshot.data.is_some());
+}
+
+#[tokio::test]
+async fn test_verification_method_and_service_updates() {
+    let mut bank = DidTestBank::start().await;
+    let authority = Keypair::new();
+    let did = bank.create_did(&authority).await.unwrap();
+    let registrar = bank.registrar(&authority.pubkey());
+
+    let instructions = [
+        registrar.add_verification_method(
+            &authority.pubkey(),
+            "key-2",
+            0,
+            authority.pubkey().to_bytes().to_vec(),
+            VM_FLAG_ASSERTION | VM_FLAG_KEY_AGREEMENT
+        ),
+        registrar.add_service(&authority.pubkey(), "agent", "TestService", "https://test-service.com", false),
+    ];
+    bank.process(&instructions, &[&authority]).await.unwrap();
+
+    let doc = bank.resolver().resolve(&did.to_string()).await.unwrap().unwrap();
+    assert_eq!(doc.verification_method.len(), 2);
+    let key2 = doc.verification_method
+        .iter()
+        .find(|vm| vm.id == did.to_did_url("key-2"))
+        .expect("key-2 not found");
+    assert!(matches!(key2.type_, VerificationMethodType::Ed25519VerificationKey2018));
+    assert_eq!(doc.key_agreement, vec![did.to_did_url("key-2")]);
+
+    assert_eq!(doc.service.len(), 1);
+    assert_eq!(doc.service[0].id, did.to_did_url("agent"));
+    let ServiceKind::Other { value } = &doc.service[0].service_endpoint else {
+        panic!("expected a generic service");
+    };
+    assert_eq!(value["serviceEndpoint"], "https://test-service.com");
+
+    // removing both restores the initial document
+    let instructions = [
+        registrar.remove_service(&authority.pubkey(), "agent"),
+        registrar.remove_verification_method(&authority.pubkey(), "key-2"),
+    ];
+    bank.process(&instructions, &[&authority]).await.unwrap();
+    let doc = bank.resolver().resolve(&did.to_string()).await.unwrap().unwrap();
+    assert_eq!(doc.verification_method.len(), 1);
+    assert!(doc.service.is_empty());
+}
+
+#[tokio::test]
+async fn test_close_reverts_to_generative_document() {
+    let mut bank = DidTestBank::start().await;
+    let authority = Keypair::new();
+    let did = bank.create_did(&authority).await.unwrap();
+    let registrar = bank.registrar(&authority.pubkey());
+
+    let close = registrar.close(&authority.pubkey(), &bank.payer().pubkey());
+    bank.process(&[close], &[&authority]).await.unwrap();
+
+    let resolver = bank.resolver();
+    let snapshot = resolver.fetch_account(&did.to_string()).await.unwrap();
+    assert!(snapshot.data.is_none());
+    let resolution = resolver.resolve_document(&did.to_string()).await.unwrap();
+    assert_eq!(resolution.did_doc.verification_method.len(), 1);
+}
+
+#[tokio::test]
+async fn test_unauthorized_update_is_rejected() {
+    let mut bank = DidTestBank::start().await;
+    let authority = Keypair::new();
+    bank.create_did(&authority).await.unwrap();
+
+    let intruder = Keypair::new();
+    let instruction = bank
+        .registrar(&authority.pubkey())
+        .add_service(&intruder.pubkey(), "agent", "TestService", "https://evil.example.com", false);
+    let result = bank.process(&[instruction], &[&intruder]).await;
+    assert!(matches!(result, Err(SolResolverError::RpcError(_))));
+}
+
+#[tokio::test]
+async fn test_min_context_slot_beyond_bank() {
+    let mut bank = DidTestBank::start().await;
+    let authority = Keypair::new();
+    let did = bank.create_did(&authority).await.unwrap();
+
+    let options = ResolveOptions::default().with_min_context_slot(u64::MAX);
+    let result = bank.resolver().resolve_document_with(&did.to_string(), &options).await;
+    assert!(matches!(result, Err(SolResolverError::RpcError(_))));
+}
+
+#[tokio::test]
+async fn test_reports_slot_of_requested_commitment() {
+    let mut bank = DidTestBank::start().await;
+    let authority = Keypair::new();
+    let did = bank.create_did(&authority).await.unwrap().to_string();
+    let resolver = bank.resolver();
+
+    let processed = ResolveOptions::default().with_commitment(CommitmentConfig::processed());
+    let processed = resolver.resolve_document_with(&did, &processed).await.unwrap();
+    let finalized = ResolveOptions::default().with_commitment(CommitmentConfig::finalized());
+    let finalized = resolver.resolve_document_with(&did, &finalized).await.unwrap();
+
+    // the working bank is ahead of the root
+    let root_slot = bank.banks_client().get_root_slot().await.unwrap();
+    assert_eq!(finalized.metadata.slot, Some(root_slot));
+    assert!(processed.metadata.slot >= finalized.metadata.slot);
+}
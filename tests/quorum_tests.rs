@@ -0,0 +1,141 @@
+mod mock_rpc;
+
+use std::sync::atomic::{ AtomicU64, Ordering };
+use didcomm_soldid_resolver::{
+    QuorumPolicy,
+    ResolveOptions,
+    SolResolver,
+    SolResolverConfig,
+    SolResolverError,
+};
+use mock_rpc::{ account, missing_account, start_mock_rpc };
+use serde_json::Value;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+const DID: &str = "did:sol:localnet:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+const OWNER: &str = "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc";
+
+// minContextSlot sent with the latest request to the first honest endpoint
+static SEEN_MIN_SLOT: AtomicU64 = AtomicU64::new(u64::MAX);
+
+fn min_context_slot(params: &Value) -> Option<u64> {
+    params[1]["minContextSlot"].as_u64()
+}
+
+#[tokio::test]
+async fn test_quorum_outvotes_lying_endpoint() {
+    let honest_url = start_mock_rpc(|_, params| {
+        SEEN_MIN_SLOT.store(min_context_slot(params).unwrap_or(0), Ordering::SeqCst);
+        Ok(account(20, OWNER, "AQID"))
+    }).await;
+    let other_honest_url = start_mock_rpc(|_, _| Ok(account(18, OWNER, "AQID"))).await;
+    let lying_url = start_mock_rpc(|_, _| Ok(account(21, OWNER, "BAUG"))).await;
+
+    let config = SolResolverConfig::default()
+        .with_rpc_urls("localnet", &[&honest_url, &lying_url, &other_honest_url])
+        .with_quorum(QuorumPolicy::new(2));
+    let resolver = SolResolver::new(config);
+
+    let snapshot = resolver.fetch_account(DID).await.unwrap();
+    assert_eq!(snapshot.data, Some(vec![1, 2, 3]));
+    assert_eq!(snapshot.slot, 20);
+    assert_eq!(SEEN_MIN_SLOT.load(Ordering::SeqCst), 0);
+
+    // later reads may not go back before the slot the quorum agreed on
+    resolver.fetch_account(DID).await.unwrap();
+    assert_eq!(SEEN_MIN_SLOT.load(Ordering::SeqCst), 20);
+}
+
+#[tokio::test]
+async fn test_quorum_disagreement_is_reported() {
+    let first_url = start_mock_rpc(|_, _| Ok(account(30, OWNER, "AQID"))).await;
+    let second_url = start_mock_rpc(|_, _| Ok(account(30, OWNER, "BAUG"))).await;
+    let failing_url = start_mock_rpc(|_, _| Err(503)).await;
+
+    let config = SolResolverConfig::default()
+        .with_rpc_urls("localnet", &[&first_url, &second_url, &failing_url])
+        .with_quorum(QuorumPolicy::new(2));
+    let resolver = SolResolver::new(config);
+
+    let disagreement = match resolver.resolve_document(DID).await {
+        Err(SolResolverError::QuorumNotReached(disagreement)) => disagreement,
+        other => panic!("expected a quorum disagreement, got {:?}", other),
+    };
+    assert_eq!(disagreement.network, "localnet");
+    assert_eq!(disagreement.required, 2);
+    assert_eq!(disagreement.agreeing, 1);
+    assert_eq!(disagreement.observations.len(), 3);
+
+    let first = &disagreement.observations[0];
+    let second = &disagreement.observations[1];
+    assert_eq!(first.url, first_url);
+    assert_eq!(first.owner.map(|owner| owner.to_string()).as_deref(), Some(OWNER));
+    assert_ne!(first.data_hash, second.data_hash);
+    assert!(disagreement.observations[2].error.is_some());
+}
+
+#[tokio::test]
+async fn test_quorum_rejects_responses_below_min_context_slot() {
+    let current_url = start_mock_rpc(|_, params| {
+        match min_context_slot(params) {
+            Some(100) => Ok(missing_account(150)),
+            _ => Err(400),
+        }
+    }).await;
+    // a stale node ignoring minContextSlot
+    let stale_url = start_mock_rpc(|_, _| Ok(missing_account(90))).await;
+
+    let quorum = QuorumPolicy::new(2).with_min_context_slot(100);
+    let config = SolResolverConfig::default()
+        .with_rpc_urls("localnet", &[&current_url, &stale_url])
+        .with_quorum(quorum.clone());
+    let resolver = SolResolver::new(config);
+
+    match resolver.fetch_account(DID).await {
+        Err(SolResolverError::QuorumNotReached(disagreement)) => {
+            assert_eq!(disagreement.agreeing, 1);
+            assert_eq!(disagreement.observations[0].slot, Some(150));
+            assert!(disagreement.observations[1].error.as_ref().unwrap().contains("below minimum"));
+        }
+        other => panic!("expected a quorum disagreement, got {:?}", other),
+    }
+
+    // both endpoints agree on the missing account once only one vote is needed
+    let config = SolResolverConfig::default()
+        .with_rpc_urls("localnet", &[&current_url, &stale_url])
+        .with_quorum(QuorumPolicy { required: 1, ..quorum });
+    let snapshot = SolResolver::new(config).fetch_account(DID).await.unwrap();
+    assert_eq!(snapshot.data, None);
+    assert_eq!(snapshot.slot, 150);
+}
+
+#[tokio::test]
+async fn test_quorum_floor_is_kept_per_commitment() {
+    // the confirmed tip runs ahead of the finalized one, which rejects later slots
+    fn handler(_: &str, params: &Value) -> Result<Value, u16> {
+        let tip = match params[1]["commitment"].as_str() {
+            Some("confirmed") => 50,
+            _ => 40,
+        };
+        match min_context_slot(params) {
+            Some(min_context_slot) if min_context_slot > tip => Err(400),
+            _ => Ok(account(tip, OWNER, "AQID")),
+        }
+    }
+    let first_url = start_mock_rpc(handler).await;
+    let second_url = start_mock_rpc(handler).await;
+
+    let config = SolResolverConfig::default()
+        .with_rpc_urls("localnet", &[&first_url, &second_url])
+        .with_quorum(QuorumPolicy::new(2));
+    let resolver = SolResolver::new(config);
+
+    let confirmed = ResolveOptions::default().with_commitment(CommitmentConfig::confirmed());
+    let snapshot = resolver.fetch_account_with(DID, &confirmed).await.unwrap();
+    assert_eq!(snapshot.slot, 50);
+
+    let snapshot = resolver.fetch_account(DID).await.unwrap();
+    assert_eq!(snapshot.slot, 40);
+    let snapshot = resolver.fetch_account_with(DID, &confirmed).await.unwrap();
+    assert_eq!(snapshot.slot, 50);
+}
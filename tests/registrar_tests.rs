@@ -0,0 +1,177 @@
+use std::str::FromStr;
+use anchor_client::anchor_lang::AnchorDeserialize;
+use didcomm_soldid_resolver::{ DidRegistrar, DidSol, SolResolver, SolResolverConfig, SolResolverError };
+use didcomm_soldid_resolver::registrar::{ account_size, VM_FLAG_AUTHENTICATION, VM_FLAG_KEY_AGREEMENT };
+use sha2::{ Digest, Sha256 };
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{ Keypair, Signer };
+use solana_sdk::system_program;
+
+const PROGRAM_ID: &str = "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc";
+
+fn registrar(authority: &Keypair) -> DidRegistrar {
+    DidRegistrar::new(
+        DidSol::new("devnet", authority.pubkey()),
+        Pubkey::from_str(PROGRAM_ID).unwrap()
+    )
+}
+
+// Anchor instruction discriminator: the first 8 bytes of sha256("global:<name>")
+fn discriminator(name: &str) -> Vec<u8> {
+    Sha256::digest(format!("global:{}", name).as_bytes())[..8].to_vec()
+}
+
+#[test]
+fn test_derives_the_resolver_did_account() {
+    let authority = Keypair::new();
+    let did = DidSol::new("devnet", authority.pubkey());
+
+    let resolver = SolResolver::new(
+        SolResolverConfig::default().with_program_id(Pubkey::from_str(PROGRAM_ID).unwrap())
+    );
+    let registrar = DidRegistrar::from_config(did.clone(), resolver.config());
+    assert_eq!(registrar.did_account(), resolver.derive_did_account(&did).0);
+    assert_eq!(registrar.program_id().to_string(), PROGRAM_ID);
+}
+
+#[test]
+fn test_initialize_instruction() {
+    let authority = Keypair::new();
+    let payer = Keypair::new();
+    let registrar = registrar(&authority);
+
+    let instruction = registrar.initialize(10_000, &payer.pubkey());
+    assert_eq!(instruction.program_id.to_string(), PROGRAM_ID);
+    assert_eq!(instruction.data[..8], discriminator("initialize"));
+    let args = sol_did::instruction::Initialize::try_from_slice(&instruction.data[8..]).unwrap();
+    assert_eq!(args.size, 10_000);
+
+    let accounts: Vec<_> = instruction.accounts
+        .iter()
+        .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+        .collect();
+    assert_eq!(accounts, vec![
+        (registrar.did_account(), false, true),
+        (authority.pubkey(), true, false),
+        (payer.pubkey(), true, true),
+        (system_program::ID, false, false)
+    ]);
+}
+
+#[test]
+fn test_verification_method_and_service_instructions() {
+    let authority = Keypair::new();
+    let key = Keypair::new();
+    let registrar = registrar(&authority);
+
+    let instruction = registrar.add_verification_method(
+        &authority.pubkey(),
+        "key-2",
+        0,
+        key.pubkey().to_bytes().to_vec(),
+        VM_FLAG_AUTHENTICATION | VM_FLAG_KEY_AGREEMENT
+    );
+    assert_eq!(instruction.data[..8], discriminator("add_verification_method"));
+    let args = sol_did::instruction::AddVerificationMethod::try_from_slice(&instruction.data[8..]).unwrap();
+    assert_eq!(args.verification_method.fragment, "key-2");
+    assert_eq!(args.verification_method.flags, 0b101);
+    assert_eq!(args.verification_method.key_data, key.pubkey().to_bytes());
+    assert!(args.eth_signature.is_none());
+    assert_eq!(instruction.accounts[0].pubkey, registrar.did_account());
+    assert!(instruction.accounts[1].is_signer);
+
+    let instruction = registrar.set_vm_flags(&authority.pubkey(), "key-2", VM_FLAG_AUTHENTICATION);
+    assert_eq!(instruction.data[..8], discriminator("set_vm_flags"));
+    let args = sol_did::instruction::SetVmFlags::try_from_slice(&instruction.data[8..]).unwrap();
+    assert_eq!(args.flags_vm.fragment, "key-2");
+    assert_eq!(args.flags_vm.flags, VM_FLAG_AUTHENTICATION);
+
+    let instruction = registrar.add_service(&authority.pubkey(), "agent", "DIDCommMessaging", "https://example.com", false);
+    assert_eq!(instruction.data[..8], discriminator("add_service"));
+    let args = sol_did::instruction::AddService::try_from_slice(&instruction.data[8..]).unwrap();
+    assert_eq!(args.service.service_endpoint, "https://example.com");
+    assert!(!args.allow_overwrite);
+
+    let instruction = registrar.remove_service(&authority.pubkey(), "agent");
+    assert_eq!(instruction.data[..8], discriminator("remove_service"));
+    let instruction = registrar.remove_verification_method(&authority.pubkey(), "key-2");
+    assert_eq!(instruction.data[..8], discriminator("remove_verification_method"));
+}
+
+#[test]
+fn test_controllers_resize_and_close_instructions() {
+    let authority = Keypair::new();
+    let controller = Keypair::new();
+    let registrar = registrar(&authority);
+
+    let instruction = registrar.set_controllers(
+        &authority.pubkey(),
+        vec![controller.pubkey()],
+        vec!["did:web:example.com".to_string()]
+    );
+    assert_eq!(instruction.data[..8], discriminator("set_controllers"));
+    let args = sol_did::instruction::SetControllers::try_from_slice(&instruction.data[8..]).unwrap();
+    assert_eq!(args.set_controllers_arg.native_controllers, vec![controller.pubkey()]);
+    assert_eq!(args.set_controllers_arg.other_controllers, vec!["did:web:example.com"]);
+
+    let instruction = registrar.resize(20_000, &authority.pubkey(), &authority.pubkey());
+    assert_eq!(instruction.data[..8], discriminator("resize"));
+
+    let destination = Pubkey::new_unique();
+    let instruction = registrar.close(&authority.pubkey(), &destination);
+    assert_eq!(instruction.data[..8], discriminator("close"));
+    assert!(instruction.accounts.iter().any(|meta| meta.pubkey == destination && meta.is_writable));
+}
+
+#[test]
+fn test_transactions() {
+    let authority = Keypair::new();
+    let payer = Keypair::new();
+    let registrar = registrar(&authority);
+    let instructions = [
+        registrar.initialize(10_000, &payer.pubkey()),
+        registrar.add_service(&authority.pubkey(), "agent", "DIDCommMessaging", "https://example.com", false),
+    ];
+
+    let unsigned = registrar.transaction(&instructions, &payer.pubkey());
+    assert_eq!(unsigned.message.account_keys[0], payer.pubkey());
+    assert_eq!(unsigned.message.header.num_required_signatures, 2);
+    assert!(!unsigned.is_signed());
+
+    let blockhash = Hash::new_unique();
+    let signed = registrar.signed_transaction(&instructions, &payer.pubkey(), &[&payer, &authority], blockhash).unwrap();
+    assert!(signed.is_signed());
+    assert!(signed.verify().is_ok());
+    assert_eq!(signed.message.recent_blockhash, blockhash);
+
+    // the DID authority must sign
+    let result = registrar.signed_transaction(&instructions, &payer.pubkey(), &[&payer], blockhash);
+    assert!(matches!(result, Err(SolResolverError::SigningError(_))));
+}
+
+#[test]
+fn test_account_size() {
+    let authority = Keypair::new();
+    let mut did_account = sol_did::state::DidAccount {
+        version: 0,
+        bump: 255,
+        nonce: 0,
+        initial_verification_method: sol_did::state::VerificationMethod {
+            fragment: "default".to_string(),
+            flags: VM_FLAG_AUTHENTICATION,
+            method_type: 0,
+            key_data: authority.pubkey().to_bytes().to_vec(),
+        },
+        verification_methods: vec![],
+        services: vec![],
+        native_controllers: vec![],
+        other_controllers: vec![],
+    };
+    let size = account_size(&did_account);
+    // discriminator, version, bump, nonce, the default key and four empty vectors
+    assert_eq!(size, 8 + 1 + 1 + 8 + (4 + 7 + 2 + 1 + 4 + 32) + 4 * 4);
+
+    did_account.native_controllers.push(Pubkey::new_unique());
+    assert_eq!(account_size(&did_account), size + 32);
+}
@@ -0,0 +1,162 @@
+mod mock_rpc;
+
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::time::Duration;
+use didcomm::did::DIDResolver;
+use didcomm_soldid_resolver::{ RetryPolicy, SolResolver, SolResolverConfig, SolResolverError };
+use mock_rpc::{ missing_account, start_mock_rpc };
+
+const DID: &str = "did:sol:localnet:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+
+static FLAKY_CALLS: AtomicUsize = AtomicUsize::new(0);
+static DOWN_CALLS: AtomicUsize = AtomicUsize::new(0);
+static BACKUP_CALLS: AtomicUsize = AtomicUsize::new(0);
+static REJECTED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: 4,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        jitter: 0.5,
+        eject_after: 2,
+        eject_duration: Duration::from_secs(60),
+    }
+}
+
+#[test]
+fn test_backoff_is_exponential_and_bounded() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(350),
+        jitter: 0.0,
+        ..Default::default()
+    };
+
+    assert_eq!(policy.backoff(0), Duration::from_millis(100));
+    assert_eq!(policy.backoff(1), Duration::from_millis(200));
+    assert_eq!(policy.backoff(2), Duration::from_millis(350));
+    assert_eq!(policy.backoff(30), Duration::from_millis(350));
+
+    let jittered = RetryPolicy { jitter: 0.5, ..policy };
+    for retry in 0..5 {
+        let delay = jittered.backoff(retry);
+        assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(350));
+    }
+}
+
+#[tokio::test]
+async fn test_retries_transient_failures() {
+    // fails twice, then answers
+    let rpc_url = start_mock_rpc(|_, _| {
+        match FLAKY_CALLS.fetch_add(1, Ordering::SeqCst) {
+            0 => Err(503),
+            1 => Err(502),
+            _ => Ok(missing_account(9)),
+        }
+    }).await;
+    let config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &rpc_url)
+        .with_retry_policy(RetryPolicy { eject_after: 10, ..fast_policy() });
+    let resolver = SolResolver::new(config);
+
+    let resolution = resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(resolution.metadata.slot, Some(9));
+
+    let health = resolver
+        .endpoint_health()
+        .into_iter()
+        .find(|h| h.network == "localnet")
+        .unwrap();
+    assert_eq!(health.total_failures, 2);
+    assert_eq!(health.total_successes, 1);
+    assert_eq!(health.consecutive_failures, 0);
+    assert!(!health.ejected);
+}
+
+#[tokio::test]
+async fn test_fails_over_and_ejects_unhealthy_endpoint() {
+    let down_url = start_mock_rpc(|_, _| {
+        DOWN_CALLS.fetch_add(1, Ordering::SeqCst);
+        Err(503)
+    }).await;
+    let backup_url = start_mock_rpc(|_, _| {
+        BACKUP_CALLS.fetch_add(1, Ordering::SeqCst);
+        Ok(missing_account(12))
+    }).await;
+    let config = SolResolverConfig::default()
+        .with_rpc_urls("localnet", &[&down_url, &backup_url])
+        .with_retry_policy(fast_policy());
+    let resolver = SolResolver::new(config);
+
+    // first attempt hits the primary, the retry fails over to the backup
+    resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(DOWN_CALLS.load(Ordering::SeqCst), 1);
+    assert_eq!(BACKUP_CALLS.load(Ordering::SeqCst), 1);
+
+    resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(DOWN_CALLS.load(Ordering::SeqCst), 2);
+
+    // two consecutive failures eject the primary, later calls go straight to the backup
+    let health = resolver.endpoint_health();
+    let primary = health.iter().find(|h| h.url == down_url).unwrap();
+    assert!(primary.ejected);
+    assert_eq!(primary.consecutive_failures, 2);
+
+    resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(DOWN_CALLS.load(Ordering::SeqCst), 2);
+    assert_eq!(BACKUP_CALLS.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let rpc_url = start_mock_rpc(|_, _| Err(500)).await;
+    let config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &rpc_url)
+        .with_retry_policy(RetryPolicy { max_attempts: 3, ..fast_policy() });
+    let resolver = SolResolver::new(config);
+
+    let result = resolver.resolve_document(DID).await;
+    assert!(matches!(result, Err(SolResolverError::RpcError(_))));
+
+    let health = resolver.endpoint_health();
+    let localnet = health.iter().find(|h| h.network == "localnet").unwrap();
+    assert_eq!(localnet.total_failures, 3);
+}
+
+#[tokio::test]
+async fn test_does_not_retry_rejected_requests() {
+    let rpc_url = start_mock_rpc(|_, _| {
+        REJECTED_CALLS.fetch_add(1, Ordering::SeqCst);
+        Err(403)
+    }).await;
+    let config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &rpc_url)
+        .with_retry_policy(RetryPolicy { max_attempts: 3, ..fast_policy() });
+    let resolver = SolResolver::new(config);
+
+    let result = resolver.resolve_document(DID).await;
+    assert!(matches!(result, Err(SolResolverError::RpcError(_))));
+    assert_eq!(REJECTED_CALLS.load(Ordering::SeqCst), 1);
+
+    // a rejected request says nothing about the endpoint's health
+    let health = resolver.endpoint_health();
+    let localnet = health.iter().find(|h| h.network == "localnet").unwrap();
+    assert_eq!(localnet.total_failures, 0);
+    assert!(!localnet.ejected);
+}
+
+#[tokio::test]
+async fn test_didcomm_resolution_falls_back_to_generative_document() {
+    let rpc_url = start_mock_rpc(|_, _| Err(500)).await;
+    let config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &rpc_url)
+        .with_retry_policy(RetryPolicy { max_attempts: 1, ..fast_policy() });
+
+    let doc = SolResolver::new(config.clone()).resolve(DID).await.unwrap().unwrap();
+    assert_eq!(doc.id, DID);
+    assert_eq!(doc.verification_method.len(), 1);
+
+    let result = SolResolver::new(config.with_strict(true)).resolve(DID).await;
+    assert!(result.is_err());
+}
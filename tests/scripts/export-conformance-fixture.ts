@@ -0,0 +1,69 @@
+import { Connection, PublicKey } from "@solana/web3.js";
+import { DidSolIdentifier, DidSolService } from "@identity.com/sol-did-client";
+import * as fs from "fs";
+import * as path from "path";
+import { TestAnchorWallet } from "./TestAnchorWallet";
+import { loadKeypair } from "./utils";
+
+const DID_SOL_PROGRAM_ID = new PublicKey(
+  "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc"
+);
+const FIXTURE_DIR = "./tests/conformance";
+
+/**
+ * Writes a conformance fixture for the DID of the test account: the raw DID account
+ * bytes as `<name>.bin` and the document `service.resolve()` returns as `<name>.json`.
+ *
+ * Usage: npm run test:export-fixture -- <name> [keypair.json]
+ */
+async function exportFixture() {
+  const [name, keypairPath = "./tests/data/test-account.json"] =
+    process.argv.slice(2);
+  if (!name) {
+    console.error("Usage: export-conformance-fixture.ts <name> [keypair.json]");
+    process.exit(1);
+  }
+
+  const connection = new Connection(
+    "http://api.devnet.solana.com",
+    "finalized"
+  );
+
+  try {
+    const authority = loadKeypair(keypairPath);
+    const anchorWallet = new TestAnchorWallet(authority);
+
+    const didIdentifier = DidSolIdentifier.create(
+      authority.publicKey,
+      "devnet"
+    );
+
+    const service = await DidSolService.build(didIdentifier, {
+      connection,
+      wallet: anchorWallet,
+    });
+
+    const [didAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("did-account"), authority.publicKey.toBuffer()],
+      DID_SOL_PROGRAM_ID
+    );
+    const accountInfo = await connection.getAccountInfo(didAccount);
+    if (!accountInfo) {
+      throw new Error(`DID account ${didAccount.toBase58()} does not exist`);
+    }
+
+    const did_doc = await service.resolve();
+
+    const binPath = path.join(FIXTURE_DIR, `${name}.bin`);
+    const jsonPath = path.join(FIXTURE_DIR, `${name}.json`);
+    fs.writeFileSync(binPath, accountInfo.data);
+    fs.writeFileSync(jsonPath, JSON.stringify(did_doc, null, 2) + "\n");
+
+    console.log(`Wrote ${binPath} and ${jsonPath} for`, did_doc.id.toString());
+  } catch (error) {
+    console.error("Error exporting fixture:", error);
+    process.exit(1);
+  }
+}
+
+exportFixture();
@@ -0,0 +1,174 @@
+mod mock_rpc;
+
+use base64::{ engine::general_purpose::URL_SAFE_NO_PAD, Engine };
+use didcomm::{ Message, PackEncryptedOptions, UnpackOptions };
+use didcomm::did::{ DIDResolver, VerificationMaterial, VerificationMethodType };
+use didcomm::secrets::{ SecretMaterial, SecretsResolver };
+use didcomm_soldid_resolver::{ DidSol, SolKeypairSecretsResolver, SolResolver, SolResolverConfig };
+use mock_rpc::{ missing_account, start_mock_rpc };
+use serde_json::{ json, Value };
+use solana_sdk::signature::{ write_keypair_file, Keypair, Signer };
+
+// every DID is chainless
+fn no_accounts(_: &str, _: &Value) -> Result<Value, u16> {
+    Ok(missing_account(1))
+}
+
+async fn key_agreement_resolver() -> SolResolver {
+    let rpc_url = start_mock_rpc(no_accounts).await;
+    SolResolver::new(
+        SolResolverConfig::default().with_rpc_url("localnet", &rpc_url).with_derive_key_agreement(true)
+    )
+}
+
+fn message(from: Option<&DidSol>, to: &DidSol) -> Message {
+    let message = Message::build(
+        "1234567890".to_string(),
+        "https://example.com/protocols/lets_do_lunch/1.0/proposal".to_string(),
+        json!({ "messagespecificattribute": "and its value" })
+    ).to(to.to_string());
+
+    match from {
+        Some(from) => message.from(from.to_string()).finalize(),
+        None => message.finalize(),
+    }
+}
+
+#[tokio::test]
+async fn test_loads_keypair_file_as_default_secrets() {
+    let keypair = Keypair::new();
+    let path = std::env::temp_dir().join(format!("soldid-keypair-{}.json", std::process::id()));
+    write_keypair_file(&keypair, &path).unwrap();
+
+    let secrets = SolKeypairSecretsResolver::from_keypair_file("localnet", &path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let did = DidSol::new("localnet", keypair.pubkey());
+    let default_id = did.to_did_url("default");
+    let key_agreement_id = did.to_did_url("default-x25519");
+    assert_eq!(secrets.secret_ids().collect::<Vec<_>>(), vec![default_id.as_str(), key_agreement_id.as_str()]);
+
+    let secret = secrets.get_secret(&default_id).await.unwrap().unwrap();
+    let SecretMaterial::JWK { private_key_jwk } = secret.secret_material else {
+        panic!("expected a JWK secret");
+    };
+    assert_eq!(private_key_jwk["crv"], "Ed25519");
+    assert_eq!(URL_SAFE_NO_PAD.decode(private_key_jwk["x"].as_str().unwrap()).unwrap(), keypair.pubkey().to_bytes());
+    assert_eq!(URL_SAFE_NO_PAD.decode(private_key_jwk["d"].as_str().unwrap()).unwrap(), keypair.to_bytes()[..32]);
+
+    let found = secrets.find_secrets(&[key_agreement_id.as_str(), "did:sol:other#default"]).await.unwrap();
+    assert_eq!(found, vec![key_agreement_id.as_str()]);
+}
+
+#[tokio::test]
+async fn test_rejects_invalid_keypair_file() {
+    let path = std::env::temp_dir().join(format!("soldid-bad-keypair-{}.json", std::process::id()));
+    std::fs::write(&path, "[1, 2, 3]").unwrap();
+
+    let result = SolKeypairSecretsResolver::from_keypair_file("localnet", &path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_derived_key_agreement_matches_secret() {
+    let keypair = Keypair::new();
+    let did = DidSol::new("localnet", keypair.pubkey());
+    let secrets = SolKeypairSecretsResolver::new().with_keypair(&did, "default", &keypair);
+
+    let resolver = key_agreement_resolver().await;
+    let did_doc = resolver.resolve(&did.to_string()).await.unwrap().unwrap();
+    let key_agreement_id = did.to_did_url("default-x25519");
+    assert_eq!(did_doc.key_agreement, vec![key_agreement_id.clone()]);
+
+    let vm = did_doc.verification_method
+        .iter()
+        .find(|vm| vm.id == key_agreement_id)
+        .unwrap();
+    assert!(matches!(vm.type_, VerificationMethodType::X25519KeyAgreementKey2019));
+    let VerificationMaterial::Base58 { public_key_base58 } = &vm.verification_material else {
+        panic!("expected a base58 key");
+    };
+
+    let secret = secrets.get_secret(&key_agreement_id).await.unwrap().unwrap();
+    let SecretMaterial::JWK { private_key_jwk } = secret.secret_material else {
+        panic!("expected a JWK secret");
+    };
+    assert_eq!(private_key_jwk["crv"], "X25519");
+    assert_eq!(
+        URL_SAFE_NO_PAD.decode(private_key_jwk["x"].as_str().unwrap()).unwrap(),
+        bs58::decode(public_key_base58).into_vec().unwrap()
+    );
+
+    // without the flag the generative document has no key agreement keys
+    let rpc_url = start_mock_rpc(no_accounts).await;
+    let plain = SolResolver::new(SolResolverConfig::default().with_rpc_url("localnet", &rpc_url));
+    let did_doc = plain.resolve(&did.to_string()).await.unwrap().unwrap();
+    assert!(did_doc.key_agreement.is_empty());
+}
+
+#[tokio::test]
+async fn test_anoncrypt_round_trip() {
+    let bob_keypair = Keypair::new();
+    let bob = DidSol::new("localnet", bob_keypair.pubkey());
+    let bob_secrets = SolKeypairSecretsResolver::new().with_keypair(&bob, "default", &bob_keypair);
+    let resolver = key_agreement_resolver().await;
+
+    let msg = message(None, &bob);
+    let (packed, metadata) = msg
+        .pack_encrypted(
+            &bob.to_string(),
+            None,
+            None,
+            &resolver,
+            &SolKeypairSecretsResolver::new(),
+            &PackEncryptedOptions::default()
+        ).await
+        .unwrap();
+    assert_eq!(metadata.to_kids, vec![bob.to_did_url("default-x25519")]);
+
+    let (unpacked, metadata) = Message::unpack(
+        &packed,
+        &resolver,
+        &bob_secrets,
+        &UnpackOptions::default()
+    ).await.unwrap();
+    assert_eq!(unpacked, msg);
+    assert!(metadata.encrypted);
+    assert!(metadata.anonymous_sender);
+}
+
+#[tokio::test]
+async fn test_authcrypt_round_trip() {
+    let alice_keypair = Keypair::new();
+    let alice = DidSol::new("localnet", alice_keypair.pubkey());
+    let bob_keypair = Keypair::new();
+    let bob = DidSol::new("localnet", bob_keypair.pubkey());
+    let secrets = SolKeypairSecretsResolver::new()
+        .with_keypair(&alice, "default", &alice_keypair)
+        .with_keypair(&bob, "default", &bob_keypair);
+    let resolver = key_agreement_resolver().await;
+
+    let msg = message(Some(&alice), &bob);
+    let (packed, metadata) = msg
+        .pack_encrypted(
+            &bob.to_string(),
+            Some(&alice.to_string()),
+            None,
+            &resolver,
+            &secrets,
+            &PackEncryptedOptions::default()
+        ).await
+        .unwrap();
+    assert_eq!(metadata.from_kid, Some(alice.to_did_url("default-x25519")));
+
+    let (unpacked, metadata) = Message::unpack(
+        &packed,
+        &resolver,
+        &secrets,
+        &UnpackOptions::default()
+    ).await.unwrap();
+    assert_eq!(unpacked, msg);
+    assert!(metadata.authenticated);
+    assert_eq!(metadata.encrypted_from_kid, Some(alice.to_did_url("default-x25519")));
+}
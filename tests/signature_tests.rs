@@ -0,0 +1,242 @@
+mod did_account_fixture;
+
+use base64::{ engine::general_purpose::URL_SAFE_NO_PAD, Engine };
+use did_account_fixture::{ did_account, fixture_resolver };
+use didcomm_soldid_resolver::registrar::VM_FLAG_ASSERTION;
+use didcomm_soldid_resolver::{ DidSol, SolResolver, SolResolverError, VerificationRelationship };
+use serde_json::json;
+use sha2::{ Digest, Sha256 };
+use sol_did::state::VerificationMethod;
+use solana_sdk::keccak;
+use solana_sdk::signature::{ Keypair, Signer };
+
+const MESSAGE: &[u8] = b"did:sol signature";
+
+fn secp256k1_key() -> libsecp256k1::SecretKey {
+    libsecp256k1::SecretKey::parse(&[3; 32]).unwrap()
+}
+
+fn ethereum_key() -> libsecp256k1::SecretKey {
+    libsecp256k1::SecretKey::parse(&[5; 32]).unwrap()
+}
+
+fn ethereum_address(key: &libsecp256k1::SecretKey) -> Vec<u8> {
+    let public_key = libsecp256k1::PublicKey::from_secret_key(key).serialize();
+    keccak::hash(&public_key[1..]).to_bytes()[12..].to_vec()
+}
+
+// A default Ed25519 key that can authenticate, a secp256k1 key and an Ethereum address
+// for assertions
+fn resolver(did: &DidSol) -> SolResolver {
+    fixture_resolver([
+        did_account(
+            did,
+            vec![
+                VerificationMethod {
+                    fragment: "secp".to_string(),
+                    flags: VM_FLAG_ASSERTION,
+                    method_type: 2,
+                    key_data: libsecp256k1::PublicKey
+                        ::from_secret_key(&secp256k1_key())
+                        .serialize_compressed()
+                        .to_vec(),
+                },
+                VerificationMethod {
+                    fragment: "eth".to_string(),
+                    flags: VM_FLAG_ASSERTION,
+                    method_type: 1,
+                    key_data: ethereum_address(&ethereum_key()),
+                }
+            ],
+            vec![]
+        ),
+    ])
+}
+
+fn sign_es256k(message: &[u8]) -> Vec<u8> {
+    let digest: [u8; 32] = Sha256::digest(message).into();
+    let (signature, _) = libsecp256k1::sign(&libsecp256k1::Message::parse(&digest), &secp256k1_key());
+    signature.serialize().to_vec()
+}
+
+// Detached JWS of `payload` with a base64url encoded payload
+fn detached_jws(alg: &str, kid: &str, payload: &[u8], sign: impl Fn(&[u8]) -> Vec<u8>) -> String {
+    let header = URL_SAFE_NO_PAD.encode(json!({ "alg": alg, "kid": kid }).to_string());
+    let signing_input = format!("{}.{}", header, URL_SAFE_NO_PAD.encode(payload));
+    format!("{}..{}", header, URL_SAFE_NO_PAD.encode(sign(signing_input.as_bytes())))
+}
+
+#[tokio::test]
+async fn test_verifies_ed25519_signature() {
+    let keypair = Keypair::new();
+    let did = DidSol::new("devnet", keypair.pubkey());
+    let signature = keypair.sign_message(MESSAGE);
+
+    let vm = resolver(&did)
+        .verify(
+            &did.to_did_url("default"),
+            MESSAGE,
+            signature.as_ref(),
+            VerificationRelationship::Authentication
+        ).await
+        .unwrap();
+    assert_eq!(vm.id, did.to_did_url("default"));
+}
+
+#[tokio::test]
+async fn test_rejects_tampered_message() {
+    let keypair = Keypair::new();
+    let did = DidSol::new("devnet", keypair.pubkey());
+    let signature = keypair.sign_message(MESSAGE);
+
+    let result = resolver(&did).verify(
+        &did.to_did_url("default"),
+        b"another message",
+        signature.as_ref(),
+        VerificationRelationship::Authentication
+    ).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidSignature(_))));
+}
+
+#[tokio::test]
+async fn test_rejects_key_outside_relationship() {
+    let keypair = Keypair::new();
+    let did = DidSol::new("devnet", keypair.pubkey());
+    let signature = keypair.sign_message(MESSAGE);
+
+    let result = resolver(&did).verify(
+        &did.to_did_url("default"),
+        MESSAGE,
+        signature.as_ref(),
+        VerificationRelationship::AssertionMethod
+    ).await;
+    assert!(
+        matches!(result, Err(SolResolverError::KeyNotAuthorized { relationship, .. }) if relationship == "assertionMethod")
+    );
+
+    let result = resolver(&did).verify(
+        &did.to_did_url("missing"),
+        MESSAGE,
+        signature.as_ref(),
+        VerificationRelationship::Authentication
+    ).await;
+    assert!(matches!(result, Err(SolResolverError::KeyNotAuthorized { .. })));
+}
+
+#[tokio::test]
+async fn test_verifies_secp256k1_signature() {
+    let did = DidSol::new("devnet", Keypair::new().pubkey());
+
+    let vm = resolver(&did)
+        .verify(
+            &did.to_did_url("secp"),
+            MESSAGE,
+            &sign_es256k(MESSAGE),
+            VerificationRelationship::AssertionMethod
+        ).await
+        .unwrap();
+    assert_eq!(vm.type_, "EcdsaSecp256k1VerificationKey2019");
+}
+
+#[tokio::test]
+async fn test_verifies_ethereum_personal_sign_signature() {
+    let did = DidSol::new("devnet", Keypair::new().pubkey());
+
+    let prefix = format!("\x19Ethereum Signed Message:\n{}", MESSAGE.len());
+    let digest = keccak::hashv(&[prefix.as_bytes(), MESSAGE]).to_bytes();
+    let (signature, recovery_id) = libsecp256k1::sign(
+        &libsecp256k1::Message::parse(&digest),
+        &ethereum_key()
+    );
+    let mut signature = signature.serialize().to_vec();
+    signature.push(recovery_id.serialize() + 27);
+
+    let resolver = resolver(&did);
+    let vm = resolver
+        .verify(&did.to_did_url("eth"), MESSAGE, &signature, VerificationRelationship::AssertionMethod).await
+        .unwrap();
+    assert_eq!(vm.type_, "EcdsaSecp256k1RecoveryMethod2020");
+
+    // recovers another address
+    let result = resolver.verify(
+        &did.to_did_url("eth"),
+        b"another message",
+        &signature,
+        VerificationRelationship::AssertionMethod
+    ).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidSignature(_))));
+}
+
+#[tokio::test]
+async fn test_verifies_detached_jws() {
+    let keypair = Keypair::new();
+    let did = DidSol::new("devnet", keypair.pubkey());
+    let resolver = resolver(&did);
+    let payload = br#"{"iss":"did:sol"}"#;
+
+    let jws = detached_jws("EdDSA", &did.to_did_url("default"), payload, |input| {
+        keypair.sign_message(input).as_ref().to_vec()
+    });
+    let vm = resolver.verify_jws(&jws, payload, VerificationRelationship::Authentication).await.unwrap();
+    assert_eq!(vm.id, did.to_did_url("default"));
+
+    let jws = detached_jws("ES256K", &did.to_did_url("secp"), payload, sign_es256k);
+    let vm = resolver.verify_jws(&jws, payload, VerificationRelationship::AssertionMethod).await.unwrap();
+    assert_eq!(vm.id, did.to_did_url("secp"));
+
+    let result = resolver.verify_jws(&jws, b"{}", VerificationRelationship::AssertionMethod).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidSignature(_))));
+}
+
+#[tokio::test]
+async fn test_verifies_unencoded_payload_jws() {
+    let keypair = Keypair::new();
+    let did = DidSol::new("devnet", keypair.pubkey());
+    let payload = b"unencoded payload";
+
+    let header = URL_SAFE_NO_PAD.encode(
+        json!({ "alg": "EdDSA", "kid": did.to_did_url("default"), "b64": false, "crit": ["b64"] }).to_string()
+    );
+    let mut signing_input = format!("{}.", header).into_bytes();
+    signing_input.extend_from_slice(payload);
+    let signature = keypair.sign_message(&signing_input);
+    let jws = format!("{}..{}", header, URL_SAFE_NO_PAD.encode(signature.as_ref()));
+
+    resolver(&did).verify_jws(&jws, payload, VerificationRelationship::Authentication).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_rejects_unencoded_payload_jws_without_crit() {
+    let keypair = Keypair::new();
+    let did = DidSol::new("devnet", keypair.pubkey());
+    let payload = b"unencoded payload";
+
+    for header in [
+        json!({ "alg": "EdDSA", "kid": did.to_did_url("default"), "b64": false }),
+        json!({ "alg": "EdDSA", "kid": did.to_did_url("default"), "b64": false, "crit": ["b64", "exp"] }),
+    ] {
+        let header = URL_SAFE_NO_PAD.encode(header.to_string());
+        let mut signing_input = format!("{}.", header).into_bytes();
+        signing_input.extend_from_slice(payload);
+        let signature = keypair.sign_message(&signing_input);
+        let jws = format!("{}..{}", header, URL_SAFE_NO_PAD.encode(signature.as_ref()));
+
+        let result = resolver(&did).verify_jws(&jws, payload, VerificationRelationship::Authentication).await;
+        assert!(matches!(result, Err(SolResolverError::InvalidSignature(_))));
+    }
+}
+
+#[tokio::test]
+async fn test_rejects_jws_algorithm_of_another_key_type() {
+    let did = DidSol::new("devnet", Keypair::new().pubkey());
+    let payload = b"payload";
+
+    let jws = detached_jws("EdDSA", &did.to_did_url("secp"), payload, sign_es256k);
+    let result = resolver(&did).verify_jws(&jws, payload, VerificationRelationship::AssertionMethod).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidSignature(_))));
+
+    // attached payloads are not detached JWS
+    let attached = jws.replacen("..", &format!(".{}.", URL_SAFE_NO_PAD.encode(payload)), 1);
+    let result = resolver(&did).verify_jws(&attached, payload, VerificationRelationship::AssertionMethod).await;
+    assert!(matches!(result, Err(SolResolverError::InvalidSignature(_))));
+}
@@ -3,7 +3,7 @@ use std::fs;
 use didcomm::did::DIDDoc;
 use didcomm::did::DIDResolver;
 use solana_sdk::signature::{ Keypair, Signer };
-use didcomm_soldid_resolver::{ SolResolver, config };
+use didcomm_soldid_resolver::{ DidSol, SolResolver, config };
 use std::sync::Once;
 
 // Used to ensure one-time initialization
@@ -32,7 +32,7 @@ impl TestContext {
         // Extract the public key and encode it in Base58
         let public_key_base58 = keypair.pubkey().to_string();
 
-        let did = "did:sol:".to_string() + network.to_string() + ":" + &public_key_base58;
+        let did = DidSol::new(network.to_string(), keypair.pubkey()).to_string();
 
         Ok(Self {
             publicKey: public_key_base58,
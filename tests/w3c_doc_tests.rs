@@ -1,4 +1,62 @@
-use didcomm_soldid_resolver::{ W3cDidDocument, w3c_doc::PublicKeyFormat };
+mod did_account_fixture;
+
+use std::str::FromStr;
+use didcomm::did::{ DIDDoc, ServiceKind, VerificationMaterial };
+use did_account_fixture::{ did_account, fixture_resolver };
+use didcomm_soldid_resolver::registrar::VM_FLAG_KEY_AGREEMENT;
+use didcomm_soldid_resolver::resolution::{ ResolutionResult, DID_CBOR };
+use didcomm_soldid_resolver::{
+    DidSol,
+    FixtureAccountSource,
+    SolResolver,
+    SolResolverConfig,
+    SolResolverError,
+    W3cDidDocument,
+    w3c_doc::PublicKeyFormat,
+};
+use serde_json::json;
+use sol_did::state::{ Service, VerificationMethod };
+use solana_sdk::pubkey::Pubkey;
+
+const WEB_DID: &str = "did:web:example.com";
+
+// A did:web document using the shorthands W3C documents may use: a single controller
+// string, relative references, an embedded authentication key and a map endpoint
+fn web_document() -> serde_json::Value {
+    json!({
+        "@context": "https://www.w3.org/ns/did/v1",
+        "id": WEB_DID,
+        "controller": WEB_DID,
+        "verificationMethod": [
+            {
+                "id": "#key-2",
+                "type": "X25519KeyAgreementKey2019",
+                "controller": WEB_DID,
+                "publicKeyBase58": "JhNWeSVLMYccCk7iopQW4guaSJTojqpMEELgSLhKwRr"
+            }
+        ],
+        "authentication": [
+            {
+                "id": format!("{}#key-1", WEB_DID),
+                "type": "EcdsaSecp256k1VerificationKey2019",
+                "controller": WEB_DID,
+                "publicKeyHex": "0x02b97c30de767f084ce3080168ee293053ba33b235d7116a3263d29f1450936b71"
+            }
+        ],
+        "keyAgreement": ["#key-2"],
+        "service": [
+            {
+                "id": "#didcomm",
+                "type": "DIDCommMessaging",
+                "serviceEndpoint": {
+                    "uri": "https://agent.example.com",
+                    "accept": ["didcomm/v2"],
+                    "routingKeys": []
+                }
+            }
+        ]
+    })
+}
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_w3c_resolve_basic_did() {
@@ -8,7 +66,7 @@ async fn test_w3c_resolve_basic_did() {
     assert!(result.is_ok());
     if let Ok(doc) = result {
         assert_eq!(doc.id, did);
-        assert!(doc.context.contains(&"https://www.w3.org/ns/did/v1".to_string()));
+        assert!(doc.context.contains(&"https://w3id.org/did/v1.0".to_string()));
     }
 }
 
@@ -20,10 +78,8 @@ async fn test_w3c_doc_context() {
     assert!(result.is_ok());
     if let Ok(doc) = result {
         assert!(doc.context.len() >= 2);
-        assert!(doc.context.contains(&"https://www.w3.org/ns/did/v1".to_string()));
-        assert!(
-            doc.context.contains(&"https://w3id.org/security/suites/ed25519-2018/v1".to_string())
-        );
+        assert!(doc.context.contains(&"https://w3id.org/did/v1.0".to_string()));
+        assert!(doc.context.contains(&"https://w3id.org/sol/v3.0".to_string()));
     }
 }
 
@@ -135,3 +191,189 @@ async fn test_w3c_default_verification_method_types() {
         );
     }
 }
+
+#[test]
+fn test_w3c_deserializes_document_shorthands() {
+    let doc: W3cDidDocument = serde_json::from_value(web_document()).unwrap();
+
+    assert_eq!(doc.context, vec!["https://www.w3.org/ns/did/v1".to_string()]);
+    assert_eq!(doc.controller, vec![WEB_DID.to_string()]);
+    // the embedded key is listed after the published ones
+    assert_eq!(doc.verification_method.len(), 2);
+    assert_eq!(doc.verification_method[1].id, format!("{}#key-1", WEB_DID));
+    assert_eq!(doc.authentication, vec![format!("{}#key-1", WEB_DID)]);
+    assert_eq!(doc.service[0].service_endpoint["uri"], "https://agent.example.com");
+}
+
+#[test]
+fn test_w3c_into_did_doc() {
+    let doc: W3cDidDocument = serde_json::from_value(web_document()).unwrap();
+    let did_doc = DIDDoc::try_from(doc).unwrap();
+
+    assert_eq!(did_doc.id, WEB_DID);
+    assert_eq!(did_doc.key_agreement, vec![format!("{}#key-2", WEB_DID)]);
+    assert_eq!(did_doc.authentication, vec![format!("{}#key-1", WEB_DID)]);
+    assert_eq!(did_doc.verification_method[0].id, format!("{}#key-2", WEB_DID));
+    assert!(
+        matches!(
+            &did_doc.verification_method[1].verification_material,
+            VerificationMaterial::Multibase { public_key_multibase } if public_key_multibase.starts_with('z')
+        )
+    );
+
+    assert_eq!(did_doc.service[0].id, format!("{}#didcomm", WEB_DID));
+    let ServiceKind::Other { value } = &did_doc.service[0].service_endpoint else {
+        panic!("Expected the W3C service to be kept as is");
+    };
+    assert_eq!(value["serviceEndpoint"]["accept"], json!(["didcomm/v2"]));
+}
+
+#[test]
+fn test_w3c_round_trips_through_did_doc() {
+    let did = DidSol::new("devnet", Pubkey::from_str("BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS").unwrap());
+    let doc = W3cDidDocument::from_did_account(&did, None);
+
+    let did_doc = DIDDoc::try_from(doc.clone()).unwrap();
+    let converted = W3cDidDocument::from(did_doc);
+
+    assert_eq!(converted.id, doc.id);
+    assert_eq!(converted.verification_method, doc.verification_method);
+    assert_eq!(converted.authentication, doc.authentication);
+    assert_eq!(converted.key_agreement, doc.key_agreement);
+    assert_eq!(converted.service, doc.service);
+}
+
+#[test]
+fn test_w3c_into_did_doc_rejects_invalid_documents() {
+    let mut document = web_document();
+    document["authentication"][0]["publicKeyHex"] = json!("0xnothex");
+    let doc: W3cDidDocument = serde_json::from_value(document).unwrap();
+    assert!(matches!(DIDDoc::try_from(doc), Err(SolResolverError::InvalidDidDocument(_))));
+
+    let mut document = web_document();
+    document["id"] = json!("example.com");
+    let doc: W3cDidDocument = serde_json::from_value(document).unwrap();
+    assert!(matches!(DIDDoc::try_from(doc), Err(SolResolverError::InvalidDidDocument(_))));
+}
+
+#[test]
+fn test_w3c_keeps_unknown_key_material() {
+    let mut document = web_document();
+    document["verificationMethod"].as_array_mut().unwrap().push(json!({
+        "id": "#account",
+        "type": "EcdsaSecp256k1RecoveryMethod2020",
+        "controller": WEB_DID,
+        "blockchainAccountId": "eip155:1:0xb9c5714089478a327f09197987f16f9e5d936e8a"
+    }));
+    let doc: W3cDidDocument = serde_json::from_value(document).unwrap();
+
+    let vm = doc.verification_method.iter().find(|vm| vm.id == "#account").unwrap();
+    assert_eq!(
+        vm.public_key,
+        PublicKeyFormat::Other(json!({ "blockchainAccountId": "eip155:1:0xb9c5714089478a327f09197987f16f9e5d936e8a" }))
+    );
+    let serialized = serde_json::to_value(vm).unwrap();
+    assert_eq!(serialized["blockchainAccountId"], "eip155:1:0xb9c5714089478a327f09197987f16f9e5d936e8a");
+}
+
+#[test]
+fn test_w3c_into_did_doc_skips_methods_without_public_key() {
+    let mut document = web_document();
+    document["verificationMethod"].as_array_mut().unwrap().push(json!({
+        "id": "#eth",
+        "type": "EcdsaSecp256k1RecoveryMethod2020",
+        "controller": WEB_DID,
+        "ethereumAddress": "0xb9c5714089478a327f09197987f16f9e5d936e8a"
+    }));
+    document["verificationMethod"].as_array_mut().unwrap().push(json!({
+        "id": "#pem",
+        "type": "JsonWebKey2020",
+        "controller": WEB_DID,
+        "publicKeyPem": "-----BEGIN PUBLIC KEY-----"
+    }));
+    document["keyAgreement"] = json!(["#key-2", "#eth", "#pem"]);
+    let doc: W3cDidDocument = serde_json::from_value(document).unwrap();
+
+    let did_doc = DIDDoc::try_from(doc).unwrap();
+    let ids: Vec<&str> = did_doc.verification_method.iter().map(|vm| vm.id.as_str()).collect();
+    assert!(!ids.contains(&format!("{}#eth", WEB_DID).as_str()));
+    assert!(!ids.contains(&format!("{}#pem", WEB_DID).as_str()));
+    assert_eq!(did_doc.key_agreement, vec![format!("{}#key-2", WEB_DID)]);
+}
+
+// Resolver reading a DID account with an authentication key and a service from a fixture
+fn resolver_with_service(did: &DidSol) -> SolResolver {
+    fixture_resolver([
+        did_account(
+            did,
+            vec![VerificationMethod {
+                fragment: "key-2".to_string(),
+                flags: VM_FLAG_KEY_AGREEMENT,
+                method_type: 0,
+                key_data: vec![7; 32],
+            }],
+            vec![Service {
+                fragment: "agent".to_string(),
+                service_type: "DIDCommMessaging".to_string(),
+                service_endpoint: "https://agent.example.com".to_string(),
+            }]
+        ),
+    ])
+}
+
+#[tokio::test]
+async fn test_w3c_resolve_with_matches_didcomm_document() {
+    let did = DidSol::new("devnet", Pubkey::new_from_array([3; 32]));
+    let resolver = resolver_with_service(&did);
+
+    let doc = W3cDidDocument::resolve_with(&resolver, &did.to_string()).await.unwrap();
+    let did_doc = resolver.resolve_document(&did.to_string()).await.unwrap().did_doc;
+
+    let vm_ids: Vec<&String> = doc.verification_method.iter().map(|vm| &vm.id).collect();
+    let did_doc_vm_ids: Vec<&String> = did_doc.verification_method.iter().map(|vm| &vm.id).collect();
+    assert_eq!(vm_ids, did_doc_vm_ids);
+    assert_eq!(doc.authentication, did_doc.authentication);
+    assert_eq!(doc.key_agreement, did_doc.key_agreement);
+    assert_eq!(doc.service.len(), 1);
+    assert_eq!(doc.service[0].id, did_doc.service[0].id);
+}
+
+#[tokio::test]
+async fn test_w3c_resolve_with_applies_resolver_config() {
+    let did = DidSol::new("devnet", Pubkey::new_from_array([3; 32])).to_string();
+    let resolver = SolResolver::new(SolResolverConfig::default().with_strict(true))
+        .with_account_source(FixtureAccountSource::new());
+
+    let result = resolver.resolve_w3c(&did).await;
+    assert!(matches!(result, Err(SolResolverError::NotFound)));
+
+    let unknown_network = "did:sol:unknown:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+    let result = W3cDidDocument::resolve_with(&resolver, unknown_network).await;
+    assert!(matches!(result, Err(SolResolverError::UnsupportedNetwork(_))));
+}
+
+#[test]
+fn test_w3c_cbor_round_trip() {
+    let doc: W3cDidDocument = serde_json::from_value(web_document()).unwrap();
+
+    let cbor = doc.to_cbor();
+    let decoded = W3cDidDocument::from_cbor(&cbor).unwrap();
+    assert_eq!(decoded, doc);
+    assert_eq!(decoded.to_cbor(), cbor);
+
+    let truncated = W3cDidDocument::from_cbor(&cbor[..cbor.len() / 2]);
+    assert!(matches!(truncated, Err(SolResolverError::InvalidDidDocument(_))));
+}
+
+#[tokio::test]
+async fn test_w3c_cbor_document_stream() {
+    let did = DidSol::new("devnet", Pubkey::new_from_array([3; 32]));
+    let resolver = resolver_with_service(&did);
+
+    let resolution = resolver.resolve_document(&did.to_string()).await;
+    let w3c_doc = resolution.as_ref().unwrap().w3c_doc.clone();
+    let result = ResolutionResult::from_resolution(resolution, DID_CBOR);
+
+    let stream = result.document_stream().unwrap();
+    assert_eq!(W3cDidDocument::from_cbor(&stream).unwrap(), w3c_doc);
+}
@@ -0,0 +1,223 @@
+mod mock_rpc;
+
+use std::str::FromStr;
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use std::time::Duration;
+use anchor_client::anchor_lang::AccountSerialize;
+use base64::{ engine::general_purpose::STANDARD, Engine };
+use didcomm_soldid_resolver::{ DidSol, SolResolver, SolResolverConfig };
+use futures::{ SinkExt, StreamExt };
+use mock_rpc::{ account, missing_account, start_mock_rpc };
+use serde_json::{ json, Value };
+use sol_did::state::{ DidAccount, VerificationMethod };
+use solana_sdk::pubkey::Pubkey;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+const DID: &str = "did:sol:localnet:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS";
+
+static RPC_CALLS: AtomicUsize = AtomicUsize::new(0);
+static MISSED_CHANGE_RPC_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// Pubsub stand-in: every connection acknowledges the account subscription, sends one
+/// notification for a closed account at the next slot and then drops the connection.
+async fn start_mock_pubsub() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let mut slot = 100;
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+            slot += 1;
+
+            while let Some(Ok(message)) = ws.next().await {
+                let Message::Text(text) = message else {
+                    continue;
+                };
+                let request: Value = serde_json::from_str(&text).unwrap();
+                if request["method"] != "accountSubscribe" {
+                    continue;
+                }
+
+                let ack = json!({ "jsonrpc": "2.0", "result": 1, "id": request["id"] });
+                ws.send(Message::Text(ack.to_string())).await.unwrap();
+
+                let notification =
+                    json!({
+                    "jsonrpc": "2.0",
+                    "method": "accountNotification",
+                    "params": {
+                        "result": {
+                            "context": { "slot": slot },
+                            "value": {
+                                "lamports": 0,
+                                "data": ["", "base64"],
+                                "owner": "11111111111111111111111111111111",
+                                "executable": false,
+                                "rentEpoch": 0,
+                                "space": 0
+                            }
+                        },
+                        "subscription": 1
+                    }
+                });
+                ws.send(Message::Text(notification.to_string())).await.unwrap();
+                let _ = ws.close(None).await;
+                break;
+            }
+        }
+    });
+
+    format!("ws://{}", addr)
+}
+
+/// Pubsub stand-in that acknowledges account subscriptions but never notifies. The first
+/// `disconnects` connections are dropped right after the acknowledgement; the others stay
+/// open and report "subscribed" and, once the client goes away, "closed".
+async fn start_silent_mock_pubsub(disconnects: usize) -> (String, mpsc::UnboundedReceiver<&'static str>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (events, receiver) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        for connection in 0.. {
+            let (socket, _) = listener.accept().await.unwrap();
+            let events = events.clone();
+
+            tokio::spawn(async move {
+                let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+                while let Some(Ok(message)) = ws.next().await {
+                    let Message::Text(text) = message else {
+                        continue;
+                    };
+                    let request: Value = serde_json::from_str(&text).unwrap();
+                    if request["method"] != "accountSubscribe" {
+                        continue;
+                    }
+
+                    let ack = json!({ "jsonrpc": "2.0", "result": 1, "id": request["id"] });
+                    ws.send(Message::Text(ack.to_string())).await.unwrap();
+                    if connection < disconnects {
+                        let _ = ws.close(None).await;
+                        return;
+                    }
+                    let _ = events.send("subscribed");
+                }
+                let _ = events.send("closed");
+            });
+        }
+    });
+
+    (format!("ws://{}", addr), receiver)
+}
+
+// base64 encoded DID account of DID with only its default key
+fn did_account_data() -> String {
+    let did = DidSol::new("localnet", Pubkey::from_str("BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS").unwrap());
+    let (_, bump) = SolResolver::default().derive_did_account(&did);
+
+    let did_account = DidAccount {
+        version: 0,
+        bump,
+        nonce: 1,
+        initial_verification_method: VerificationMethod {
+            fragment: "default".to_string(),
+            flags: 1 << 6,
+            method_type: 0,
+            key_data: did.pubkey().to_bytes().to_vec(),
+        },
+        verification_methods: vec![],
+        services: vec![],
+        native_controllers: vec![],
+        other_controllers: vec![],
+    };
+    let mut data = vec![];
+    did_account.try_serialize(&mut data).unwrap();
+    STANDARD.encode(data)
+}
+
+#[test]
+fn test_websocket_url_derivation() {
+    let config = SolResolverConfig::default();
+    assert_eq!(config.networks["localnet"].websocket_url(), "ws://127.0.0.1:8900");
+    assert_eq!(config.networks["devnet"].websocket_url(), "wss://api.devnet.solana.com");
+
+    let config = config.with_ws_url("devnet", "wss://pubsub.example.com");
+    assert_eq!(config.networks["devnet"].websocket_url(), "wss://pubsub.example.com");
+}
+
+#[tokio::test]
+async fn test_watch_reconnects_and_invalidates_cache() {
+    let rpc_url = start_mock_rpc(|_, _| {
+        RPC_CALLS.fetch_add(1, Ordering::SeqCst);
+        Ok(missing_account(100))
+    }).await;
+    let ws_url = start_mock_pubsub().await;
+    let config = SolResolverConfig::default()
+        .with_rpc_url("localnet", &rpc_url)
+        .with_ws_url("localnet", &ws_url)
+        .with_cache_ttl(Duration::from_secs(600));
+    let resolver = SolResolver::new(config);
+
+    // the second resolution is served from the cache
+    resolver.resolve_document(DID).await.unwrap();
+    resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(RPC_CALLS.load(Ordering::SeqCst), 1);
+
+    let mut updates = Box::pin(resolver.watch(DID).unwrap());
+
+    // each notification arrives on a fresh connection
+    let first = tokio::time::timeout(Duration::from_secs(10), updates.next()).await.unwrap().unwrap().unwrap();
+    let second = tokio::time::timeout(Duration::from_secs(10), updates.next()).await.unwrap().unwrap().unwrap();
+    assert_eq!(first.slot, 101);
+    assert_eq!(second.slot, 102);
+    assert_eq!(second.did_doc.id, DID);
+    assert_eq!(second.metadata.slot, Some(102));
+
+    // the change dropped the cached document; every subscription also read the account once
+    let rpc_calls = RPC_CALLS.load(Ordering::SeqCst);
+    resolver.resolve_document(DID).await.unwrap();
+    assert_eq!(RPC_CALLS.load(Ordering::SeqCst), rpc_calls + 1);
+}
+
+#[tokio::test]
+async fn test_watch_reports_change_missed_while_disconnected() {
+    // the account is created between the first and the second subscription
+    let rpc_url = start_mock_rpc(|_, _| {
+        if MISSED_CHANGE_RPC_CALLS.fetch_add(1, Ordering::SeqCst) == 0 {
+            Ok(missing_account(200))
+        } else {
+            Ok(account(205, "didso1Dpqpm4CsiCjzP766BGY89CAdD6ZBL68cRhFPc", &did_account_data()))
+        }
+    }).await;
+    let (ws_url, _events) = start_silent_mock_pubsub(1).await;
+    let config = SolResolverConfig::default().with_rpc_url("localnet", &rpc_url).with_ws_url("localnet", &ws_url);
+
+    let mut updates = Box::pin(SolResolver::new(config).watch(DID).unwrap());
+    let update = tokio::time::timeout(Duration::from_secs(10), updates.next()).await.unwrap().unwrap().unwrap();
+    assert_eq!(update.slot, 205);
+    assert_eq!(update.did_doc.id, DID);
+}
+
+#[tokio::test]
+async fn test_dropping_stream_closes_idle_subscription() {
+    let rpc_url = start_mock_rpc(|_, _| Ok(missing_account(300))).await;
+    let (ws_url, mut events) = start_silent_mock_pubsub(0).await;
+    let config = SolResolverConfig::default().with_rpc_url("localnet", &rpc_url).with_ws_url("localnet", &ws_url);
+
+    let updates = SolResolver::new(config).watch(DID).unwrap();
+    let event = tokio::time::timeout(Duration::from_secs(10), events.recv()).await.unwrap();
+    assert_eq!(event, Some("subscribed"));
+
+    drop(updates);
+    let event = tokio::time::timeout(Duration::from_secs(10), events.recv()).await.unwrap();
+    assert_eq!(event, Some("closed"));
+}
+
+#[tokio::test]
+async fn test_watch_rejects_invalid_did() {
+    assert!(SolResolver::default().watch("did:sol:not-a-key").is_err());
+}
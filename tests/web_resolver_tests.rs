@@ -0,0 +1,170 @@
+use didcomm::did::{ DIDResolver, VerificationMaterial, VerificationMethodType };
+use didcomm_soldid_resolver::{ CompositeResolver, WebResolver };
+use tokio::io::{ AsyncReadExt, AsyncWriteExt };
+use tokio::net::TcpListener;
+
+/// Binds a local port, builds the did:web identifier for `path` on it and serves the
+/// document produced by `body` for every request. Returns the DID.
+async fn serve_document(status: &'static str, path: &str, body: fn(&str) -> String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let did = format!("did:web:127.0.0.1%3A{}{}", port, path);
+    let body = body(&did);
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await;
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/did+json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+
+    did
+}
+
+fn web_document(did: &str) -> String {
+    serde_json
+        ::json!({
+            "@context": "https://www.w3.org/ns/did/v1",
+            "id": did,
+            "alsoKnownAs": ["did:sol:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS"],
+            "verificationMethod": [
+                {
+                    "id": format!("{}#key-1", did),
+                    "type": "Ed25519VerificationKey2018",
+                    "controller": did,
+                    "publicKeyBase58": "BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS"
+                },
+                {
+                    "id": format!("{}#key-2", did),
+                    "type": "X25519KeyAgreementKey2020",
+                    "controller": did,
+                    "publicKeyMultibase": "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc"
+                }
+            ],
+            "authentication": [format!("{}#key-1", did)],
+            "keyAgreement": [format!("{}#key-2", did)],
+            "service": [
+                {
+                    "id": format!("{}#agent", did),
+                    "type": "DIDCommMessaging",
+                    "serviceEndpoint": "https://agent.example.com"
+                }
+            ]
+        })
+        .to_string()
+}
+
+#[test]
+fn test_did_web_document_url() {
+    let resolver = WebResolver::default();
+
+    assert_eq!(
+        resolver.document_url("did:web:example.com").unwrap(),
+        "https://example.com/.well-known/did.json"
+    );
+    assert_eq!(
+        resolver.document_url("did:web:example.com:user:alice").unwrap(),
+        "https://example.com/user/alice/did.json"
+    );
+    assert_eq!(
+        resolver.document_url("did:web:localhost%3A8443").unwrap(),
+        "https://localhost:8443/.well-known/did.json"
+    );
+    // every segment is percent-decoded, then encoded as a path segment again
+    assert_eq!(
+        resolver.document_url("did:web:example.com:user%20a:caf%C3%A9").unwrap(),
+        "https://example.com/user%20a/caf%C3%A9/did.json"
+    );
+    assert_eq!(
+        resolver.document_url("did:web:example.com:%7Ealice").unwrap(),
+        "https://example.com/~alice/did.json"
+    );
+    assert!(resolver.document_url("did:web:example.com:a%2Fb").is_err());
+    assert!(resolver.document_url("did:web:example.com:..").is_err());
+    assert!(resolver.document_url("did:web:attacker.example%2Fx").is_err());
+    assert!(resolver.document_url("did:web:user%40example.com").is_err());
+    assert!(resolver.document_url("did:web:example.com::alice").is_err());
+    assert!(resolver.document_url("did:web:").is_err());
+    assert!(resolver.document_url("did:sol:example.com").is_err());
+}
+
+#[tokio::test]
+async fn test_resolve_path_based_did_web() {
+    let did = serve_document("200 OK", ":user:alice", web_document).await;
+    let resolver = WebResolver::default().with_scheme("http");
+
+    let doc = resolver.resolve(&did).await.unwrap().unwrap();
+    assert_eq!(doc.id, did);
+    assert_eq!(doc.verification_method.len(), 2);
+    assert_eq!(doc.authentication, vec![format!("{}#key-1", did)]);
+    assert_eq!(doc.key_agreement, vec![format!("{}#key-2", did)]);
+
+    assert!(matches!(doc.verification_method[0].type_, VerificationMethodType::Ed25519VerificationKey2018));
+    assert!(
+        matches!(
+            &doc.verification_method[0].verification_material,
+            VerificationMaterial::Base58 { public_key_base58 } if public_key_base58 == "BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS"
+        )
+    );
+    assert!(matches!(doc.verification_method[1].type_, VerificationMethodType::X25519KeyAgreementKey2020));
+    assert!(
+        matches!(
+            &doc.verification_method[1].verification_material,
+            VerificationMaterial::Multibase { public_key_multibase } if public_key_multibase.starts_with('z')
+        )
+    );
+
+    assert_eq!(doc.service.len(), 1);
+    assert_eq!(doc.service[0].id, format!("{}#agent", did));
+}
+
+#[tokio::test]
+async fn test_resolve_well_known_did_web_w3c() {
+    let did = serve_document("200 OK", "", web_document).await;
+    let resolver = WebResolver::default().with_scheme("http");
+
+    let doc = resolver.resolve_w3c(&did).await.unwrap().unwrap();
+    assert_eq!(doc.context, vec!["https://www.w3.org/ns/did/v1".to_string()]);
+    assert_eq!(doc.also_known_as, vec!["did:sol:BYJ3xJ9spKsmHqS7d3VejkPhLizqn9ZzE3QjaQp7iTuS"]);
+}
+
+#[tokio::test]
+async fn test_resolve_missing_did_web() {
+    let did = serve_document("404 Not Found", "", |_| String::new()).await;
+    let resolver = WebResolver::default().with_scheme("http");
+
+    assert!(resolver.resolve(&did).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_resolve_did_web_id_mismatch() {
+    let did = serve_document("200 OK", "", |_| web_document("did:web:attacker.example")).await;
+    let resolver = WebResolver::default().with_scheme("http");
+
+    assert!(resolver.resolve(&did).await.is_err());
+}
+
+#[tokio::test]
+async fn test_composite_resolver_dispatch() {
+    let did = serve_document("200 OK", "", web_document).await;
+    let resolver = CompositeResolver::new().with_resolver(
+        "web",
+        WebResolver::default().with_scheme("http")
+    );
+
+    let doc = resolver.resolve(&did).await.unwrap().unwrap();
+    assert_eq!(doc.id, did);
+
+    let result = resolver.resolve("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").await;
+    assert!(matches!(result, Err(e) if e.kind() == didcomm::error::ErrorKind::Unsupported));
+
+    assert!(resolver.resolve("not-a-did").await.is_err());
+}